@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	pallet_ocw::DataSource, AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
+	OcwModuleConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -59,6 +59,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Offchain worker authorities
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
 				true,
 			)
 		},
@@ -107,6 +109,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Offchain worker authorities
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
 				true,
 			)
 		},
@@ -124,12 +131,20 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
+/// The endpoints the offchain worker reads the DOT price from until root changes them.
+fn ocw_data_sources() -> Vec<DataSource> {
+	vec![DataSource::new("https://min-api.cryptocompare.com/data/price?fsym=DOT&tsyms=USD", "USD")
+		.and_then(|source| source.with_header("User-Agent", "Substrate-Offchain-Worker"))
+		.expect("default data source fits its bounds")]
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	ocw_authorities: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +167,9 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		ocw_module: OcwModuleConfig {
+			authorities: ocw_authorities,
+			data_sources: ocw_data_sources(),
+		},
 	}
 }
@@ -0,0 +1,156 @@
+//! RPC access to the data the OCW pallet indexes off chain.
+//!
+//! The node only keeps that data when started with `--enable-offchain-indexing true`. Serving it
+//! reads many block bodies, so it is an unsafe RPC.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use node_template_runtime::{
+	opaque::Block,
+	pallet_ocw::{indexing_key, IndexingData},
+	BlockNumber,
+};
+use sc_client_api::BlockBackend;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Hash, Header},
+};
+
+/// Most blocks one `ocw_indexedData` call may span.
+const MAX_BLOCK_RANGE: BlockNumber = 1_000;
+
+/// An `IndexingData` entry written by the OCW pallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEntry {
+	/// The block of the extrinsic that wrote the entry.
+	pub block_number: BlockNumber,
+	/// The index of that extrinsic in its block.
+	pub extrinsic_index: u32,
+	/// What kind of call wrote the entry, e.g. `submit_number_unsigned`.
+	pub label: String,
+	/// The indexed number.
+	pub value: u64,
+}
+
+/// Reads the entries `submit_data_unsigned_with_signed_payload` indexed off chain.
+#[rpc(client, server)]
+pub trait OcwIndexApi {
+	/// The entries indexed by blocks `from..=to` of the canonical chain, in block and extrinsic
+	/// order. Entries indexed by blocks of other forks are left out.
+	#[method(name = "ocw_indexedData")]
+	fn indexed_data(&self, from: BlockNumber, to: BlockNumber) -> RpcResult<Vec<IndexedEntry>>;
+}
+
+/// Provides RPC methods to read offchain-indexed OCW data.
+pub struct OcwIndex<C, S> {
+	client: Arc<C>,
+	storage: S,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C, S> OcwIndex<C, S> {
+	/// Creates a new instance reading from the node's offchain `storage`.
+	pub fn new(client: Arc<C>, storage: S, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, storage, deny_unsafe }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// `from` is after `to`, or the range spans more than `MAX_BLOCK_RANGE` blocks.
+	InvalidRange,
+	/// Reading a block failed.
+	ClientError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::InvalidRange => 1,
+			Error::ClientError => 2,
+		}
+	}
+}
+
+fn client_error(e: sp_blockchain::Error) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::ClientError.into(),
+		"Unable to read block.",
+		Some(format!("{:?}", e)),
+	))
+}
+
+#[async_trait]
+impl<C, S> OcwIndexApiServer for OcwIndex<C, S>
+where
+	C: HeaderBackend<Block> + BlockBackend<Block> + Send + Sync + 'static,
+	S: OffchainStorage + 'static,
+{
+	fn indexed_data(&self, from: BlockNumber, to: BlockNumber) -> RpcResult<Vec<IndexedEntry>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		if from > to || to - from >= MAX_BLOCK_RANGE {
+			return Err(CallError::Custom(ErrorObject::owned(
+				Error::InvalidRange.into(),
+				"Invalid block range.",
+				Some(format!("At most {} blocks with from <= to.", MAX_BLOCK_RANGE)),
+			))
+			.into())
+		}
+
+		let mut entries = Vec::new();
+		for block_number in from..=to {
+			// Blocks past the best one have no canonical hash yet.
+			let hash = match self.client.hash(block_number).map_err(client_error)? {
+				Some(hash) => hash,
+				None => break,
+			};
+			let parent_hash = match self.client.header(BlockId::Hash(hash)).map_err(client_error)? {
+				Some(header) => *header.parent_hash(),
+				None => break,
+			};
+			// Pruned bodies leave nothing to look up.
+			let extrinsics = self
+				.client
+				.block_body(&BlockId::Hash(hash))
+				.map_err(client_error)?
+				.unwrap_or_default();
+
+			for (extrinsic_index, extrinsic) in extrinsics.iter().enumerate() {
+				let extrinsic_index = extrinsic_index as u32;
+				let key = indexing_key(parent_hash, BlakeTwo256::hash_of(extrinsic));
+				let data = match self.storage.get(STORAGE_PREFIX, &key) {
+					Some(data) => data,
+					None => continue,
+				};
+				match IndexingData::decode(&mut &data[..]) {
+					Ok(IndexingData(label, value)) => entries.push(IndexedEntry {
+						block_number,
+						extrinsic_index,
+						label: String::from_utf8_lossy(&label).into_owned(),
+						value,
+					}),
+					Err(e) => log::warn!(
+						"Undecodable OCW data at block {} extrinsic {}: {:?}",
+						block_number,
+						extrinsic_index,
+						e
+					),
+				}
+			}
+		}
+
+		Ok(entries)
+	}
+}
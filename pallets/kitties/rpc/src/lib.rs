@@ -0,0 +1,125 @@
+//! RPC interface for the kitties pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, DeserializeOwned, RpcResult, Serialize},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_kitties::KittyDetails;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_kitties_runtime_api::KittiesApi as KittiesRuntimeApi;
+
+/// Most kitties returned by one `kitties_kittiesOf` call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, BlockNumber> {
+	/// Up to `limit` kitties of `owner`, at most `MAX_PAGE_SIZE`. Pass the last kitty of a page
+	/// as `start_after` to get the next one.
+	#[method(name = "kitties_kittiesOf")]
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<KittyIndex>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyIndex>>;
+
+	/// The kitty with its owner and the traits decoded from its DNA.
+	#[method(name = "kitties_kittyDetails")]
+	fn kitty_details(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, KittyIndex, BlockNumber>>>;
+
+	/// How many kitties were ever created or bred, burned ones included.
+	#[method(name = "kitties_count")]
+	fn kitty_count(&self, at: Option<BlockHash>) -> RpcResult<KittyIndex>;
+}
+
+/// Provides RPC methods to query kitties.
+pub struct Kitties<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Kitties<C, Block> {
+	/// Creates a new instance of the kitties RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query kitties.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, KittyIndex, BlockNumber>
+	KittiesApiServer<<Block as BlockT>::Hash, AccountId, KittyIndex, BlockNumber> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, BlockNumber>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	KittyIndex: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<KittyIndex>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<KittyIndex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitties_of(&at, owner, start_after, limit.min(MAX_PAGE_SIZE))
+			.map_err(runtime_error)
+	}
+
+	fn kitty_details(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, KittyIndex, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty_details(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn kitty_count(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<KittyIndex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty_count(&at).map_err(runtime_error)
+	}
+}
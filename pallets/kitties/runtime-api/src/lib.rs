@@ -0,0 +1,38 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_kitties::{Genome, Kitty, KittyDetails};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, BlockNumber>
+	where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		BlockNumber: Codec,
+	{
+		/// The stored kitty, including its parents and birth block.
+		fn kitty(kitty_id: KittyIndex) -> Option<Kitty<KittyIndex, BlockNumber>>;
+
+		/// The typed traits decoded from the kitty's DNA.
+		fn genome(kitty_id: KittyIndex) -> Option<Genome>;
+
+		/// Up to `limit` kitties of `owner`, starting after `start_after`, the last kitty of the
+		/// previous page.
+		fn kitties_of(
+			owner: AccountId,
+			start_after: Option<KittyIndex>,
+			limit: u32,
+		) -> Vec<KittyIndex>;
+
+		/// The kitty with its owner and decoded traits.
+		fn kitty_details(
+			kitty_id: KittyIndex,
+		) -> Option<KittyDetails<AccountId, KittyIndex, BlockNumber>>;
+
+		/// How many kitties were ever created or bred, burned ones included.
+		fn kitty_count() -> KittyIndex;
+	}
+}
@@ -0,0 +1,237 @@
+//! Benchmarking setup for pallet-kitties
+//!
+//! Ownership is tracked in O(1) maps, so the worst cases are driven by the auction queues and by
+//! calls that touch every kitty-keyed map at once rather than by how many kitties an owner holds.
+
+use super::*;
+
+use crate::pallet::BalanceOf;
+#[allow(unused)]
+use crate::Pallet as KittiesModule;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, Get, Hooks},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	funded::<T>(whitelisted_caller())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	funded::<T>(account(name, index, 0))
+}
+
+fn create_kitty<T: Config>(owner: &T::AccountId) -> T::KittyIndex {
+	let kitty_id = NextKittyId::<T>::get();
+	KittiesModule::<T>::create(RawOrigin::Signed(owner.clone()).into()).unwrap();
+	kitty_id
+}
+
+fn start_auction<T: Config>(seller: &T::AccountId, kitty_id: T::KittyIndex, end: T::BlockNumber) {
+	KittiesModule::<T>::create_auction(
+		RawOrigin::Signed(seller.clone()).into(),
+		kitty_id,
+		100u32.into(),
+		end,
+	)
+	.unwrap();
+}
+
+/// Leave room for a single more auction ending at `end`.
+fn fill_auctions_ending<T: Config>(end: T::BlockNumber) {
+	let ending = (0..T::MaxAuctionsPerBlock::get() - 1)
+		.map(|i| T::KittyIndex::max_value() - i.into())
+		.collect::<Vec<_>>();
+	AuctionsEnding::<T>::insert(end, BoundedVec::try_from(ending).unwrap());
+}
+
+fn in_blocks<T: Config>(n: u32) -> T::BlockNumber {
+	frame_system::Pallet::<T>::block_number() + n.into()
+}
+
+benchmarks! {
+	create {
+		let caller = funded_caller::<T>();
+		let kitty_id = NextKittyId::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(KittyOwner::<T>::get(kitty_id), Some(caller));
+	}
+
+	breed {
+		let caller = funded_caller::<T>();
+		let kitty_id_1 = create_kitty::<T>(&caller);
+		let kitty_id_2 = create_kitty::<T>(&caller);
+		let kitty_id = NextKittyId::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id_1, kitty_id_2)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(kitty_id), Some(caller));
+	}
+
+	transfer {
+		let caller = funded_caller::<T>();
+		let to = funded_account::<T>("to", 0);
+		let kitty_id = create_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), kitty_id, to.clone())
+	verify {
+		assert_eq!(KittyOwner::<T>::get(kitty_id), Some(to));
+	}
+
+	set_price {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), kitty_id, 100u32.into())
+	verify {
+		assert_eq!(KittyPrices::<T>::get(kitty_id), Some(100u32.into()));
+	}
+
+	clear_price {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		KittiesModule::<T>::set_price(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			100u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(KittyPrices::<T>::get(kitty_id), None);
+	}
+
+	buy {
+		let seller = funded_account::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&seller);
+		KittiesModule::<T>::set_price(RawOrigin::Signed(seller).into(), kitty_id, 100u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 100u32.into())
+	verify {
+		assert_eq!(KittyOwner::<T>::get(kitty_id), Some(caller));
+	}
+
+	create_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let end = in_blocks::<T>(1);
+		fill_auctions_ending::<T>(end);
+	}: _(RawOrigin::Signed(caller), kitty_id, 100u32.into(), end)
+	verify {
+		assert!(Auctions::<T>::contains_key(kitty_id));
+	}
+
+	bid {
+		let seller = funded_account::<T>("seller", 0);
+		let bidder = funded_account::<T>("bidder", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&seller);
+		start_auction::<T>(&seller, kitty_id, in_blocks::<T>(1));
+		KittiesModule::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 100u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 200u32.into())
+	verify {
+		let best_bid = Auctions::<T>::get(kitty_id).and_then(|auction| auction.best_bid);
+		assert_eq!(best_bid, Some((caller, 200u32.into())));
+	}
+
+	cancel_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let end = in_blocks::<T>(1);
+		fill_auctions_ending::<T>(end);
+		start_auction::<T>(&caller, kitty_id, end);
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(!Auctions::<T>::contains_key(kitty_id));
+	}
+
+	burn {
+		let caller = funded_caller::<T>();
+		let approved = funded_account::<T>("approved", 0);
+		let kitty_id = create_kitty::<T>(&caller);
+		KittiesModule::<T>::set_price(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			100u32.into(),
+		)?;
+		KittiesModule::<T>::approve(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			Some(approved),
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(!Kitties::<T>::contains_key(kitty_id));
+	}
+
+	approve {
+		let owner = funded_account::<T>("owner", 0);
+		let approved = funded_account::<T>("approved", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&owner);
+		KittiesModule::<T>::set_approval_for_all(
+			RawOrigin::Signed(owner).into(),
+			caller.clone(),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(approved.clone()))
+	verify {
+		assert_eq!(KittyApprovals::<T>::get(kitty_id), Some(approved));
+	}
+
+	set_approval_for_all {
+		let caller = funded_caller::<T>();
+		let operator = funded_account::<T>("operator", 0);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(OperatorApprovals::<T>::contains_key(caller, operator));
+	}
+
+	transfer_from {
+		let owner = funded_account::<T>("owner", 0);
+		let to = funded_account::<T>("to", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&owner);
+		KittiesModule::<T>::set_approval_for_all(
+			RawOrigin::Signed(owner.clone()).into(),
+			caller.clone(),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller), owner, to.clone(), kitty_id)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(kitty_id), Some(to));
+	}
+
+	on_initialize {
+		let n in 0 .. T::MaxAuctionsPerBlock::get();
+		let bidder = funded_account::<T>("bidder", 0);
+		let end = in_blocks::<T>(1);
+		let mut kitty_ids = Vec::new();
+		for i in 0..n {
+			let seller = funded_account::<T>("seller", i);
+			let kitty_id = create_kitty::<T>(&seller);
+			start_auction::<T>(&seller, kitty_id, end);
+			KittiesModule::<T>::bid(
+				RawOrigin::Signed(bidder.clone()).into(),
+				kitty_id,
+				100u32.into(),
+			)?;
+			kitty_ids.push(kitty_id);
+		}
+	}: {
+		KittiesModule::<T>::on_initialize(end);
+	}
+	verify {
+		for kitty_id in kitty_ids {
+			assert_eq!(KittyOwner::<T>::get(kitty_id), Some(bidder.clone()));
+		}
+	}
+
+	impl_benchmark_test_suite!(KittiesModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
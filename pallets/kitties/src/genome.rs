@@ -0,0 +1,126 @@
+//! Typed traits decoded from a kitty's 16 DNA bytes.
+//!
+//! Byte 0 selects the coat color, byte 1 the pattern, byte 2 the eye shape and byte 3 the rarity
+//! tier. The remaining bytes are carried through breeding but have no trait assigned yet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Color {
+	Black,
+	White,
+	Ginger,
+	Grey,
+	Cream,
+	Cinnamon,
+	Lilac,
+	Chocolate,
+}
+
+impl Color {
+	fn from_gene(gene: u8) -> Self {
+		match gene % 8 {
+			0 => Color::Black,
+			1 => Color::White,
+			2 => Color::Ginger,
+			3 => Color::Grey,
+			4 => Color::Cream,
+			5 => Color::Cinnamon,
+			6 => Color::Lilac,
+			_ => Color::Chocolate,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Pattern {
+	Solid,
+	Tabby,
+	Spotted,
+	Tortoiseshell,
+	Calico,
+	Pointed,
+}
+
+impl Pattern {
+	fn from_gene(gene: u8) -> Self {
+		match gene % 6 {
+			0 => Pattern::Solid,
+			1 => Pattern::Tabby,
+			2 => Pattern::Spotted,
+			3 => Pattern::Tortoiseshell,
+			4 => Pattern::Calico,
+			_ => Pattern::Pointed,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EyeShape {
+	Round,
+	Almond,
+	Slanted,
+	Sleepy,
+}
+
+impl EyeShape {
+	fn from_gene(gene: u8) -> Self {
+		match gene % 4 {
+			0 => EyeShape::Round,
+			1 => EyeShape::Almond,
+			2 => EyeShape::Slanted,
+			_ => EyeShape::Sleepy,
+		}
+	}
+}
+
+/// How rare a kitty is. Roughly 75% of genes are common, 19% uncommon, 5% rare and under 1%
+/// legendary.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Rarity {
+	Common,
+	Uncommon,
+	Rare,
+	Legendary,
+}
+
+impl Rarity {
+	fn from_gene(gene: u8) -> Self {
+		match gene {
+			0..=191 => Rarity::Common,
+			192..=239 => Rarity::Uncommon,
+			240..=253 => Rarity::Rare,
+			_ => Rarity::Legendary,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Genome {
+	pub color: Color,
+	pub pattern: Pattern,
+	pub eye_shape: EyeShape,
+	pub rarity: Rarity,
+	pub generation: u16,
+}
+
+impl Genome {
+	pub fn from_dna(dna: &[u8; 16], generation: u16) -> Self {
+		Genome {
+			color: Color::from_gene(dna[0]),
+			pattern: Pattern::from_gene(dna[1]),
+			eye_shape: EyeShape::from_gene(dna[2]),
+			rarity: Rarity::from_gene(dna[3]),
+			generation,
+		}
+	}
+}
@@ -0,0 +1,116 @@
+//! Implementations of the `nonfungible(s)` token traits, so that other pallets can hold and move
+//! kitties without knowing about this pallet's dispatchables.
+//!
+//! Kitties form a single collection, identified by `()`. Transfers and burns go through the same
+//! helpers as the extrinsics, so deposits, listings and approvals are handled identically.
+
+use crate::{Auctions, Config, Kitty, Pallet};
+use frame_support::traits::tokens::{nonfungible, nonfungibles};
+use sp_runtime::{DispatchResult, TokenError};
+use sp_std::vec::Vec;
+
+impl<T: Config> nonfungibles::Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = T::KittyIndex;
+	type CollectionId = ();
+
+	fn owner(_collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+		Self::kitty_owner(item)
+	}
+
+	/// The raw DNA is exposed under the `dna` key.
+	fn attribute(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		match key {
+			b"dna" => Self::kitties(item).map(|kitty| kitty.dna.to_vec()),
+			_ => None,
+		}
+	}
+
+	/// Kitties being auctioned are locked until the auction settles.
+	fn can_transfer(_collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		!Auctions::<T>::contains_key(item)
+	}
+}
+
+impl<T: Config> nonfungibles::Create<T::AccountId> for Pallet<T> {
+	/// There is only ever the one kitty collection, so no new ones can be created.
+	fn create_collection(
+		_collection: &Self::CollectionId,
+		_who: &T::AccountId,
+		_admin: &T::AccountId,
+	) -> DispatchResult {
+		Err(TokenError::Unsupported.into())
+	}
+}
+
+impl<T: Config> nonfungibles::Mutate<T::AccountId> for Pallet<T> {
+	/// Mint a generation 0 kitty with random DNA, reserving the deposit from `who`.
+	fn mint_into(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		let kitty = Kitty {
+			dna: Self::random_value(who),
+			generation: 0,
+			parents: None,
+			birth_block: <frame_system::Pallet<T>>::block_number(),
+		};
+		Self::mint(who.clone(), *item, kitty)
+	}
+
+	fn burn(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		Self::do_burn(*item, maybe_check_owner)
+	}
+}
+
+impl<T: Config> nonfungibles::Transfer<T::AccountId> for Pallet<T> {
+	/// The destination must be able to reserve the kitty deposit.
+	fn transfer(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::kitty_owner(item).ok_or(TokenError::UnknownAsset)?;
+		Self::do_transfer(owner, destination.clone(), *item)
+	}
+}
+
+impl<T: Config> nonfungible::Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = T::KittyIndex;
+
+	fn owner(item: &Self::ItemId) -> Option<T::AccountId> {
+		<Self as nonfungibles::Inspect<_>>::owner(&(), item)
+	}
+
+	fn attribute(item: &Self::ItemId, key: &[u8]) -> Option<Vec<u8>> {
+		<Self as nonfungibles::Inspect<_>>::attribute(&(), item, key)
+	}
+
+	fn can_transfer(item: &Self::ItemId) -> bool {
+		<Self as nonfungibles::Inspect<_>>::can_transfer(&(), item)
+	}
+}
+
+impl<T: Config> nonfungible::Mutate<T::AccountId> for Pallet<T> {
+	fn mint_into(item: &Self::ItemId, who: &T::AccountId) -> DispatchResult {
+		<Self as nonfungibles::Mutate<_>>::mint_into(&(), item, who)
+	}
+
+	fn burn(item: &Self::ItemId, maybe_check_owner: Option<&T::AccountId>) -> DispatchResult {
+		<Self as nonfungibles::Mutate<_>>::burn(&(), item, maybe_check_owner)
+	}
+}
+
+impl<T: Config> nonfungible::Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(item: &Self::ItemId, destination: &T::AccountId) -> DispatchResult {
+		<Self as nonfungibles::Transfer<_>>::transfer(&(), item, destination)
+	}
+}
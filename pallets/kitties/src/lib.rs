@@ -11,15 +11,38 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod genome;
+mod impl_nonfungibles;
+pub mod migrations;
+pub mod weights;
+
+pub use genome::Genome;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::traits::{Randomness, ReservableCurrency};
+	use frame_support::traits::{
+		BalanceStatus, ExistenceRequirement, Randomness, ReservableCurrency,
+	};
 	use frame_support::{pallet_prelude::*, traits::Currency};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AtLeast32Bit, Bounded, CheckedAdd};
+	use sp_runtime::{
+		traits::{AtLeast32Bit, Bounded, CheckedAdd, Saturating},
+		Permill,
+	};
+	use sp_std::vec::Vec;
+
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
+
+	use crate::{genome::Genome, migrations, WeightInfo};
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
-	type BalanceOf<T> =
+	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::type_value]
@@ -28,7 +51,55 @@ pub mod pallet {
 	}
 
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
-	pub struct Kitty(pub [u8; 16]);
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct Kitty<KittyIndex, BlockNumber> {
+		pub dna: [u8; 16],
+		/// 0 for created kitties, one more than the older parent for bred ones.
+		pub generation: u16,
+		/// The two kitties this one was bred from, if any.
+		pub parents: Option<(KittyIndex, KittyIndex)>,
+		pub birth_block: BlockNumber,
+	}
+
+	impl<KittyIndex, BlockNumber> Kitty<KittyIndex, BlockNumber> {
+		pub fn genome(&self) -> Genome {
+			Genome::from_dna(&self.dna, self.generation)
+		}
+	}
+
+	pub type KittyOf<T> =
+		Kitty<<T as Config>::KittyIndex, <T as frame_system::Config>::BlockNumber>;
+
+	/// A kitty together with its owner and decoded traits, as served by the runtime API.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct KittyDetails<AccountId, KittyIndex, BlockNumber> {
+		pub owner: AccountId,
+		pub kitty: Kitty<KittyIndex, BlockNumber>,
+		pub genome: Genome,
+	}
+
+	pub type KittyDetailsOf<T> = KittyDetails<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::KittyIndex,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// An English auction for a kitty. The best bidder has `bid + KittyPrice` reserved until they
+	/// are outbid or the auction is settled.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub struct Auction<AccountId, Balance, BlockNumber> {
+		pub seller: AccountId,
+		pub reserve_price: Balance,
+		pub end: BlockNumber,
+		pub best_bid: Option<(AccountId, Balance)>,
+	}
+
+	type AuctionOf<T> = Auction<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -40,14 +111,32 @@ pub mod pallet {
 		type KittyIndex: AtLeast32Bit + Copy + Parameter + Default + Bounded + MaxEncodedLen;
 
 		#[pallet::constant]
-		type MaxKittyIndex: Get<u32>;
+		type KittyPrice: Get<BalanceOf<Self>>;
 
+		/// The longest an auction may run, in blocks.
 		#[pallet::constant]
-		type KittyPrice: Get<BalanceOf<Self>>;
+		type MaxAuctionDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of auctions that can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// Blocks a generation 0 kitty has to rest after breeding. The rest period grows by the
+		/// same amount with every generation.
+		#[pallet::constant]
+		type BreedCooldown: Get<Self::BlockNumber>;
+
+		/// The chance that a bred kitty gets one random bit of its DNA flipped.
+		#[pallet::constant]
+		type MutationChance: Get<Permill>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -57,29 +146,103 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, Kitty>;
+	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, KittyOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId>;
 
+	/// The kitties held by each account, keyed so that membership changes are O(1).
 	#[pallet::storage]
-	#[pallet::getter(fn all_kitties)]
-	pub type AllKitties<T: Config> = StorageMap<
+	pub type OwnedKitties<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		BoundedVec<T::KittyIndex, T::MaxKittyIndex>,
+		Blake2_128Concat,
+		T::KittyIndex,
+		(),
+		OptionQuery,
+	>;
+
+	/// Number of kitties held by each account.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties_count)]
+	pub type OwnedKittiesCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Asking price of every kitty currently listed for sale.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_prices)]
+	pub type KittyPrices<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, BalanceOf<T>>;
+
+	/// The block from which a kitty may breed again. Kitties without an entry are ready.
+	#[pallet::storage]
+	#[pallet::getter(fn ready_to_breed_at)]
+	pub type ReadyToBreedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, T::BlockNumber>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, AuctionOf<T>>;
+
+	/// Auctions to settle in `on_initialize`, keyed by their end block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending)]
+	pub type AuctionsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<T::KittyIndex, T::MaxAuctionsPerBlock>,
 		ValueQuery,
 	>;
 
+	/// The single account allowed to transfer a kitty on its owner's behalf.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approvals)]
+	pub type KittyApprovals<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId>;
+
+	/// Operators allowed to manage every kitty of an owner. [owner, operator]
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		KittyCreated(T::AccountId, T::KittyIndex, Kitty),
+		KittyCreated(T::AccountId, T::KittyIndex, KittyOf<T>),
 		KittyTransferred(T::AccountId, T::AccountId, T::KittyIndex),
+		/// A kitty was put up for sale. [owner, kitty_id, price]
+		KittyListed(T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// A kitty was taken off the market. [owner, kitty_id]
+		KittyDelisted(T::AccountId, T::KittyIndex),
+		/// A listed kitty was bought. [seller, buyer, kitty_id, price]
+		KittySold(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// An auction was opened. [seller, kitty_id, reserve_price, end]
+		AuctionStarted(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+		/// A new best bid was placed. [bidder, kitty_id, amount]
+		BidPlaced(T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// An auction ended and the kitty went to the best bidder. [seller, buyer, kitty_id,
+		/// amount]
+		AuctionSettled(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// An auction ended without a sale and the kitty stays with the seller. [seller, kitty_id]
+		AuctionCancelled(T::AccountId, T::KittyIndex),
+		/// A kitty was burned and its deposit returned. [owner, kitty_id]
+		KittyBurned(T::AccountId, T::KittyIndex),
+		/// The approved account of a kitty was set or cleared. [owner, approved, kitty_id]
+		Approval(T::AccountId, Option<T::AccountId>, T::KittyIndex),
+		/// An operator was granted or denied access to all of an owner's kitties. [owner,
+		/// operator, approved]
+		ApprovalForAll(T::AccountId, T::AccountId, bool),
 	}
 
 	// Errors inform users that something went wrong.
@@ -90,7 +253,56 @@ pub mod pallet {
 		SameKittyId,
 		NotOwner,
 		NotEnoughBalance,
-		OwnTooManyKitties,
+		/// The kitty is not listed for sale.
+		NotForSale,
+		/// An owner cannot buy their own kitty.
+		BuyOwnKitty,
+		/// The asking price is above the `max_price` the buyer agreed to pay.
+		PriceTooHigh,
+		/// The kitty is being auctioned and cannot be transferred or listed.
+		KittyOnAuction,
+		/// There is no auction for this kitty.
+		AuctionNotFound,
+		/// The auction end must be in the future and within `MaxAuctionDuration`.
+		InvalidAuctionEnd,
+		/// Too many auctions already end in the requested block.
+		TooManyAuctionsEnding,
+		/// The auction has already ended.
+		AuctionEnded,
+		/// A seller cannot bid on their own auction.
+		BidOnOwnAuction,
+		/// The bid is below the reserve price or does not beat the best bid.
+		BidTooLow,
+		/// An auction that already has bids cannot be cancelled.
+		AuctionHasBids,
+		/// The kitty bred too recently and is still resting.
+		KittyOnCooldown,
+		/// A kitty cannot breed with one of its own parents.
+		BreedWithParent,
+		/// A kitty cannot breed with a kitty sharing one of its parents.
+		BreedWithSibling,
+		/// The caller is neither the owner, the approved account nor an operator of the owner.
+		NotApproved,
+		/// An owner cannot approve themselves.
+		ApproveToSelf,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>().saturating_add(migrations::v2::migrate::<T>())
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = AuctionsEnding::<T>::take(now);
+			let count = ending.len() as u32;
+
+			for kitty_id in ending {
+				Self::settle_auction(kitty_id);
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -98,7 +310,7 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::create())]
 		#[frame_support::transactional]
 		pub fn create(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -109,28 +321,17 @@ pub mod pallet {
 			let kitty_id = Self::get_next_id().map_err(|_| Error::<T>::InvalidKittyId)?;
 
 			let dna = Self::random_value(&who);
-			let kitty = Kitty(dna);
-
-			T::Currency::reserve(&who, kitty_price)?;
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
-			let next_kitty_id = kitty_id
-				.checked_add(&(T::KittyIndex::from(1_u8)))
-				.ok_or(Error::<T>::KittyIdOverflow)
-				.unwrap();
-			NextKittyId::<T>::set(next_kitty_id);
-
-			AllKitties::<T>::try_mutate(&who, |ref mut kitties| {
-				kitties.try_push(kitty_id).map_err(|_| Error::<T>::OwnTooManyKitties)?;
-				Ok::<(), DispatchError>(())
-			})?;
-
-			Self::deposit_event(Event::KittyCreated(who, kitty_id, kitty));
-
-			Ok(())
+			let kitty = Kitty {
+				dna,
+				generation: 0,
+				parents: None,
+				birth_block: <frame_system::Pallet<T>>::block_number(),
+			};
+
+			Self::mint(who, kitty_id, kitty)
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::breed())]
 		#[frame_support::transactional]
 		pub fn breed(
 			origin: OriginFor<T>,
@@ -145,77 +346,309 @@ pub mod pallet {
 			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
 			let kitty_1 = Self::get_kitty(kitty_id_1).map_err(|_| Error::<T>::InvalidKittyId)?;
 			let kitty_2 = Self::get_kitty(kitty_id_2).map_err(|_| Error::<T>::InvalidKittyId)?;
+			ensure!(
+				Self::kitty_owner(kitty_id_1) == Some(who.clone()) &&
+					Self::kitty_owner(kitty_id_2) == Some(who.clone()),
+				Error::<T>::NotOwner
+			);
+
+			Self::ensure_not_related(kitty_id_1, &kitty_1, kitty_id_2, &kitty_2)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			for kitty_id in [kitty_id_1, kitty_id_2] {
+				if let Some(ready_at) = Self::ready_to_breed_at(kitty_id) {
+					ensure!(now >= ready_at, Error::<T>::KittyOnCooldown);
+				}
+			}
 
 			let kitty_id = Self::get_next_id().map_err(|_| Error::<T>::InvalidKittyId)?;
 
 			let selector = Self::random_value(&who);
 
 			let mut data = [0u8; 16];
-			for i in 0..kitty_1.0.len() {
-				data[i] = (kitty_1.0[i] & selector[i]) | (kitty_2.0[i] & !selector[i]);
+			for i in 0..kitty_1.dna.len() {
+				data[i] = (kitty_1.dna[i] & selector[i]) | (kitty_2.dna[i] & !selector[i]);
 			}
-			let new_kitty = Kitty(data);
+			Self::maybe_mutate(&mut data, &who);
+
+			let new_kitty = Kitty {
+				dna: data,
+				generation: kitty_1.generation.max(kitty_2.generation).saturating_add(1),
+				parents: Some((kitty_id_1, kitty_id_2)),
+				birth_block: now,
+			};
+
+			ReadyToBreedAt::<T>::insert(
+				kitty_id_1,
+				now.saturating_add(Self::breed_cooldown(&kitty_1)),
+			);
+			ReadyToBreedAt::<T>::insert(
+				kitty_id_2,
+				now.saturating_add(Self::breed_cooldown(&kitty_2)),
+			);
 
-			T::Currency::reserve(&who, kitty_price)?;
-			Kitties::<T>::insert(kitty_id, &new_kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
-			let next_kitty_id = kitty_id
-				.checked_add(&(T::KittyIndex::from(1_u8)))
-				.ok_or(Error::<T>::KittyIdOverflow)
-				.unwrap();
-			NextKittyId::<T>::set(next_kitty_id);
+			Self::mint(who, kitty_id, new_kitty)
+		}
 
-			AllKitties::<T>::try_mutate(&who, |ref mut kitties| {
-				kitties.try_push(kitty_id).map_err(|_| Error::<T>::OwnTooManyKitties)?;
-				Ok::<(), DispatchError>(())
-			})?;
+		#[pallet::weight(T::WeightInfo::transfer())]
+		#[frame_support::transactional]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transfer(who, new_owner, kitty_id)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_price())]
+		pub fn set_price(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyOnAuction);
+
+			KittyPrices::<T>::insert(kitty_id, price);
+
+			Self::deposit_event(Event::KittyListed(who, kitty_id, price));
+
+			Ok(())
+		}
 
-			Self::deposit_event(Event::KittyCreated(who, kitty_id, new_kitty));
+		#[pallet::weight(T::WeightInfo::clear_price())]
+		pub fn clear_price(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(KittyPrices::<T>::contains_key(kitty_id), Error::<T>::NotForSale);
+
+			KittyPrices::<T>::remove(kitty_id);
+
+			Self::deposit_event(Event::KittyDelisted(who, kitty_id));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		/// Buy a listed kitty. The buyer pays the asking price to the seller and takes over the
+		/// `KittyPrice` deposit; the call fails if the price was raised above `max_price`.
+		#[pallet::weight(T::WeightInfo::buy())]
 		#[frame_support::transactional]
-		pub fn transfer(
+		pub fn buy(
 			origin: OriginFor<T>,
 			kitty_id: T::KittyIndex,
-			new_owner: T::AccountId,
+			max_price: BalanceOf<T>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let buyer = ensure_signed(origin)?;
+
+			let seller = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(buyer != seller, Error::<T>::BuyOwnKitty);
+
+			let price = Self::kitty_prices(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
 
 			let kitty_price = T::KittyPrice::get();
 			ensure!(
-				T::Currency::can_reserve(&new_owner, kitty_price),
+				T::Currency::can_reserve(&buyer, price.saturating_add(kitty_price)),
 				Error::<T>::NotEnoughBalance
 			);
 
-			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
+			T::Currency::transfer(&buyer, &seller, price, ExistenceRequirement::KeepAlive)?;
+			T::Currency::unreserve(&seller, kitty_price);
+			T::Currency::reserve(&buyer, kitty_price)?;
+			Self::move_kitty(&seller, &buyer, kitty_id);
 
+			Self::deposit_event(Event::KittySold(seller, buyer, kitty_id, price));
+
+			Ok(())
+		}
+
+		/// Put a kitty up for auction until block `end`. Any fixed-price listing is withdrawn.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		#[frame_support::transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			reserve_price: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
 			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyOnAuction);
 
-			T::Currency::unreserve(&who, kitty_price);
-			T::Currency::reserve(&new_owner, kitty_price)?;
-			KittyOwner::<T>::insert(kitty_id, &new_owner);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				end > now && end <= now.saturating_add(T::MaxAuctionDuration::get()),
+				Error::<T>::InvalidAuctionEnd
+			);
 
-			AllKitties::<T>::try_mutate(&who, |ref mut kitties| {
-				let index = kitties.iter().position(|&r| r == kitty_id).unwrap();
-				kitties.remove(index);
-				Ok::<(), DispatchError>(())
-			})?;
-			AllKitties::<T>::try_mutate(&new_owner, |ref mut kitties| {
-				kitties.try_push(kitty_id).map_err(|_| Error::<T>::OwnTooManyKitties)?;
+			AuctionsEnding::<T>::try_mutate(end, |ref mut ending| {
+				ending.try_push(kitty_id).map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
 				Ok::<(), DispatchError>(())
 			})?;
+			KittyPrices::<T>::remove(kitty_id);
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: who.clone(), reserve_price, end, best_bid: None },
+			);
+
+			Self::deposit_event(Event::AuctionStarted(who, kitty_id, reserve_price, end));
+
+			Ok(())
+		}
+
+		/// Bid on an auction. `amount + KittyPrice` is reserved from the bidder and the previous
+		/// best bidder is refunded straight away.
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[frame_support::transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < auction.end,
+				Error::<T>::AuctionEnded
+			);
+			ensure!(auction.seller != who, Error::<T>::BidOnOwnAuction);
+			ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+			if let Some((_, best)) = &auction.best_bid {
+				ensure!(amount > *best, Error::<T>::BidTooLow);
+			}
+
+			let kitty_price = T::KittyPrice::get();
+			if let Some((bidder, best)) = auction.best_bid.take() {
+				T::Currency::unreserve(&bidder, best.saturating_add(kitty_price));
+			}
+
+			let lock = amount.saturating_add(kitty_price);
+			ensure!(T::Currency::can_reserve(&who, lock), Error::<T>::NotEnoughBalance);
+			T::Currency::reserve(&who, lock)?;
+
+			auction.best_bid = Some((who.clone(), amount));
+			Auctions::<T>::insert(kitty_id, auction);
+
+			Self::deposit_event(Event::BidPlaced(who, kitty_id, amount));
+
+			Ok(())
+		}
+
+		/// Withdraw an auction that has not received any bids yet.
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::KittyTransferred(who, new_owner, kitty_id));
+			let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller == who, Error::<T>::NotOwner);
+			ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(kitty_id);
+			AuctionsEnding::<T>::mutate(auction.end, |ending| ending.retain(|&id| id != kitty_id));
+
+			Self::deposit_event(Event::AuctionCancelled(who, kitty_id));
 
 			Ok(())
 		}
+
+		/// Remove a kitty from storage for good and unreserve its `KittyPrice` deposit.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_burn(kitty_id, Some(&who))
+		}
+
+		/// Let `approved` transfer `kitty_id` on the owner's behalf, or clear the approval with
+		/// `None`. Callable by the owner or one of the owner's operators.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			approved: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				who == owner || OperatorApprovals::<T>::contains_key(&owner, &who),
+				Error::<T>::NotApproved
+			);
+			ensure!(approved.as_ref() != Some(&owner), Error::<T>::ApproveToSelf);
+
+			match &approved {
+				Some(account) => KittyApprovals::<T>::insert(kitty_id, account),
+				None => KittyApprovals::<T>::remove(kitty_id),
+			}
+
+			Self::deposit_event(Event::Approval(owner, approved, kitty_id));
+
+			Ok(())
+		}
+
+		/// Grant or revoke `operator` the right to transfer and approve every kitty of the caller.
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(who != operator, Error::<T>::ApproveToSelf);
+
+			if approved {
+				OperatorApprovals::<T>::insert(&who, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
+
+			Ok(())
+		}
+
+		/// Transfer `kitty_id` from `from` to `to` on behalf of `from`. The caller must be the
+		/// owner, the kitty's approved account or an operator of the owner.
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[frame_support::transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(from.clone()), Error::<T>::NotOwner);
+			ensure!(
+				who == from ||
+					Self::kitty_approvals(kitty_id) == Some(who.clone()) ||
+					OperatorApprovals::<T>::contains_key(&from, &who),
+				Error::<T>::NotApproved
+			);
+
+			Self::do_transfer(from, to, kitty_id)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
-		fn random_value(sender: &T::AccountId) -> [u8; 16] {
+		/// The decoded traits of a kitty, used by the runtime API.
+		pub fn genome(kitty_id: T::KittyIndex) -> Option<Genome> {
+			Self::kitties(kitty_id).map(|kitty| kitty.genome())
+		}
+
+		pub(crate) fn random_value(sender: &T::AccountId) -> [u8; 16] {
 			let payload = (
 				T::Randomness::random_seed(),
 				&sender,
@@ -225,6 +658,49 @@ pub mod pallet {
 			payload.using_encoded(sp_io::hashing::blake2_128)
 		}
 
+		/// Flip one random bit of `dna` with probability `MutationChance`.
+		fn maybe_mutate(dna: &mut [u8; 16], sender: &T::AccountId) {
+			let payload = (
+				T::Randomness::random(&b"kitties/mutation"[..]).0,
+				&sender,
+				<frame_system::Pallet<T>>::extrinsic_index(),
+			);
+			let roll = payload.using_encoded(sp_io::hashing::blake2_128);
+
+			let draw = u32::from_le_bytes([roll[0], roll[1], roll[2], roll[3]]) % 1_000_000;
+			if draw < T::MutationChance::get().deconstruct() {
+				let bit = roll[4] % 128;
+				dna[(bit / 8) as usize] ^= 1 << (bit % 8);
+			}
+		}
+
+		/// How long `kitty` rests after breeding: `BreedCooldown * (generation + 1)`.
+		fn breed_cooldown(kitty: &KittyOf<T>) -> T::BlockNumber {
+			T::BreedCooldown::get().saturating_mul((kitty.generation as u32 + 1).into())
+		}
+
+		fn ensure_not_related(
+			kitty_id_1: T::KittyIndex,
+			kitty_1: &KittyOf<T>,
+			kitty_id_2: T::KittyIndex,
+			kitty_2: &KittyOf<T>,
+		) -> DispatchResult {
+			let is_parent_of = |parent: T::KittyIndex, child: &KittyOf<T>| match child.parents {
+				Some((p1, p2)) => p1 == parent || p2 == parent,
+				None => false,
+			};
+			ensure!(
+				!is_parent_of(kitty_id_1, kitty_2) && !is_parent_of(kitty_id_2, kitty_1),
+				Error::<T>::BreedWithParent
+			);
+
+			if let (Some((a1, a2)), Some((b1, b2))) = (kitty_1.parents, kitty_2.parents) {
+				ensure!(a1 != b1 && a1 != b2 && a2 != b1 && a2 != b2, Error::<T>::BreedWithSibling);
+			}
+
+			Ok(())
+		}
+
 		fn get_next_id() -> Result<T::KittyIndex, ()> {
 			let kitty_id = Self::next_kitty_id();
 			match kitty_id {
@@ -233,7 +709,171 @@ pub mod pallet {
 			}
 		}
 
-		fn get_kitty(kitty_id: T::KittyIndex) -> Result<Kitty, ()> {
+		/// Every kitty held by `owner`, in storage order.
+		pub fn kitties_of(owner: &T::AccountId) -> Vec<T::KittyIndex> {
+			OwnedKitties::<T>::iter_key_prefix(owner).collect()
+		}
+
+		/// Up to `limit` kitties of `owner` in storage order, starting after `start_after`. Pass
+		/// the last kitty of a page to get the next one.
+		pub fn kitties_of_page(
+			owner: &T::AccountId,
+			start_after: Option<T::KittyIndex>,
+			limit: u32,
+		) -> Vec<T::KittyIndex> {
+			let kitties = match start_after {
+				Some(kitty_id) => OwnedKitties::<T>::iter_key_prefix_from(
+					owner,
+					OwnedKitties::<T>::hashed_key_for(owner, kitty_id),
+				),
+				None => OwnedKitties::<T>::iter_key_prefix(owner),
+			};
+			kitties.take(limit as usize).collect()
+		}
+
+		/// The kitty with its owner and decoded traits, used by the runtime API.
+		pub fn kitty_details(kitty_id: T::KittyIndex) -> Option<KittyDetailsOf<T>> {
+			let kitty = Self::kitties(kitty_id)?;
+			let owner = Self::kitty_owner(kitty_id)?;
+			Some(KittyDetails { owner, genome: kitty.genome(), kitty })
+		}
+
+		/// How many kitties were ever created or bred, burned ones included.
+		pub fn kitty_count() -> T::KittyIndex {
+			Self::next_kitty_id()
+		}
+
+		fn add_owned(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+			OwnedKitties::<T>::insert(owner, kitty_id, ());
+			OwnedKittiesCount::<T>::mutate(owner, |count| *count = count.saturating_add(1));
+		}
+
+		fn remove_owned(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+			if OwnedKitties::<T>::take(owner, kitty_id).is_some() {
+				OwnedKittiesCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
+			}
+		}
+
+		/// Store a new kitty under `kitty_id` for `who`, reserving its deposit and moving
+		/// `NextKittyId` past it. Shared by `create`, `breed` and `nonfungibles::Mutate`.
+		pub(crate) fn mint(
+			who: T::AccountId,
+			kitty_id: T::KittyIndex,
+			kitty: KittyOf<T>,
+		) -> DispatchResult {
+			ensure!(!Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			let next_kitty_id = kitty_id
+				.checked_add(&(T::KittyIndex::from(1_u8)))
+				.ok_or(Error::<T>::KittyIdOverflow)?;
+
+			T::Currency::reserve(&who, T::KittyPrice::get())?;
+			Kitties::<T>::insert(kitty_id, &kitty);
+			KittyOwner::<T>::insert(kitty_id, &who);
+			if next_kitty_id > Self::next_kitty_id() {
+				NextKittyId::<T>::set(next_kitty_id);
+			}
+
+			Self::add_owned(&who, kitty_id);
+
+			Self::deposit_event(Event::KittyCreated(who, kitty_id, kitty));
+
+			Ok(())
+		}
+
+		/// Remove `kitty_id` for good and unreserve its deposit. When `maybe_check_owner` is
+		/// given, it must be the current owner.
+		pub(crate) fn do_burn(
+			kitty_id: T::KittyIndex,
+			maybe_check_owner: Option<&T::AccountId>,
+		) -> DispatchResult {
+			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(*check_owner == owner, Error::<T>::NotOwner);
+			}
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyOnAuction);
+
+			Kitties::<T>::remove(kitty_id);
+			KittyOwner::<T>::remove(kitty_id);
+			KittyPrices::<T>::remove(kitty_id);
+			ReadyToBreedAt::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+			Self::remove_owned(&owner, kitty_id);
+			T::Currency::unreserve(&owner, T::KittyPrice::get());
+
+			Self::deposit_event(Event::KittyBurned(owner, kitty_id));
+
+			Ok(())
+		}
+
+		/// Move `kitty_id` and its deposit from `from` to `to`. Shared by `transfer`,
+		/// `transfer_from` and `nonfungibles::Transfer`, which check who may act for `from`.
+		pub(crate) fn do_transfer(
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: T::KittyIndex,
+		) -> DispatchResult {
+			let kitty_price = T::KittyPrice::get();
+			ensure!(T::Currency::can_reserve(&to, kitty_price), Error::<T>::NotEnoughBalance);
+
+			Self::get_kitty(kitty_id).map_err(|_| Error::<T>::InvalidKittyId)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(from.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyOnAuction);
+
+			T::Currency::unreserve(&from, kitty_price);
+			T::Currency::reserve(&to, kitty_price)?;
+			Self::move_kitty(&from, &to, kitty_id);
+
+			Self::deposit_event(Event::KittyTransferred(from, to, kitty_id));
+
+			Ok(())
+		}
+
+		/// Hand `kitty_id` over from `from` to `to` in `KittyOwner` and `OwnedKitties`, dropping
+		/// any sale listing and approval. Moving the reserved deposit is left to the caller.
+		fn move_kitty(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
+			KittyOwner::<T>::insert(kitty_id, to);
+			KittyPrices::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+
+			Self::remove_owned(from, kitty_id);
+			Self::add_owned(to, kitty_id);
+		}
+
+		/// Close an auction that reached its end block. The best bid is paid to the seller and the
+		/// bidder's extra reserve becomes the kitty deposit; without a bid the kitty stays with the
+		/// seller.
+		fn settle_auction(kitty_id: T::KittyIndex) {
+			let auction = match Auctions::<T>::take(kitty_id) {
+				Some(auction) => auction,
+				None => return,
+			};
+			let kitty_price = T::KittyPrice::get();
+
+			match auction.best_bid {
+				Some((buyer, amount)) => {
+					Self::move_kitty(&auction.seller, &buyer, kitty_id);
+					let _ = T::Currency::repatriate_reserved(
+						&buyer,
+						&auction.seller,
+						amount,
+						BalanceStatus::Free,
+					);
+					T::Currency::unreserve(&auction.seller, kitty_price);
+
+					Self::deposit_event(Event::AuctionSettled(
+						auction.seller,
+						buyer,
+						kitty_id,
+						amount,
+					));
+				},
+				None => Self::deposit_event(Event::AuctionCancelled(auction.seller, kitty_id)),
+			}
+		}
+
+		fn get_kitty(kitty_id: T::KittyIndex) -> Result<KittyOf<T>, ()> {
 			match Self::kitties(kitty_id) {
 				Some(kitty) => Ok(kitty),
 				None => Err(()),
@@ -0,0 +1,67 @@
+//! Storage migrations for the kitties pallet.
+
+use crate::{Config, Kitties, Kitty, OwnedKitties, OwnedKittiesCount, Pallet};
+use codec::Decode;
+use frame_support::{
+	storage::migration::storage_key_iter,
+	traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+	Blake2_128Concat,
+};
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+
+pub mod v1 {
+	use super::*;
+
+	/// The `Kitty` layout before v1: nothing but the raw DNA.
+	#[derive(Decode)]
+	pub struct OldKitty(pub [u8; 16]);
+
+	/// Wrap every stored DNA in the v1 `Kitty` struct. Kitties from before the upgrade have no
+	/// recorded parents, so they become generation 0 born at block 0.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated = 0u64;
+		Kitties::<T>::translate::<OldKitty, _>(|_, old| {
+			translated += 1;
+			Some(Kitty { dna: old.0, generation: 0, parents: None, birth_block: Zero::zero() })
+		});
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Move every account's `AllKitties` vector into `OwnedKitties` and `OwnedKittiesCount`,
+	/// removing the old entries as they are read.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut reads = 1u64;
+		let mut writes = 1u64;
+		let old = storage_key_iter::<T::AccountId, Vec<T::KittyIndex>, Blake2_128Concat>(
+			Pallet::<T>::name().as_bytes(),
+			b"AllKitties",
+		);
+		for (owner, kitties) in old.drain() {
+			reads += 1;
+			writes += 2 + kitties.len() as u64;
+			for kitty_id in kitties.iter() {
+				OwnedKitties::<T>::insert(&owner, kitty_id, ());
+			}
+			OwnedKittiesCount::<T>::insert(&owner, kitties.len() as u32);
+		}
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
@@ -8,6 +8,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -24,6 +25,7 @@ frame_support::construct_runtime!(
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		Escrow: pallet_escrow::{Pallet, Call, Storage},
 	}
 );
 
@@ -71,14 +73,80 @@ impl pallet_randomness_collective_flip::Config for Test {}
 
 parameter_types! {
 	pub const KittyPrice: u64 = 10;
+	pub static MutationChance: Permill = Permill::zero();
 }
 impl pallet_kitties::Config for Test {
 	type Event = Event;
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type KittyIndex = u32;
-	type MaxKittyIndex = ConstU32<3>;
 	type KittyPrice = KittyPrice;
+	type MaxAuctionDuration = ConstU64<100>;
+	type MaxAuctionsPerBlock = ConstU32<2>;
+	type BreedCooldown = ConstU64<10>;
+	type MutationChance = MutationChance;
+	type WeightInfo = ();
+}
+
+/// A minimal escrow pallet that only knows kitties through the `nonfungibles` traits.
+#[frame_support::pallet]
+pub mod pallet_escrow {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::tokens::nonfungibles::{Inspect, Transfer},
+	};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Items: Transfer<Self::AccountId, CollectionId = (), ItemId = u32>;
+		type EscrowAccount: Get<Self::AccountId>;
+	}
+
+	/// The depositor of every item held in escrow.
+	#[pallet::storage]
+	pub type Deposits<T: Config> = StorageMap<_, Twox64Concat, u32, T::AccountId>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NotOwner,
+		NotDepositor,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(0)]
+		pub fn deposit(origin: OriginFor<T>, item: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::Items::owner(&(), &item) == Some(who.clone()), Error::<T>::NotOwner);
+			T::Items::transfer(&(), &item, &T::EscrowAccount::get())?;
+			Deposits::<T>::insert(item, who);
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn release(origin: OriginFor<T>, item: u32, to: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Deposits::<T>::get(item) == Some(who), Error::<T>::NotDepositor);
+			T::Items::transfer(&(), &item, &to)?;
+			Deposits::<T>::remove(item);
+
+			Ok(())
+		}
+	}
+}
+
+pub const ESCROW_ACCOUNT: u64 = 5;
+
+impl pallet_escrow::Config for Test {
+	type Items = KittiesModule;
+	type EscrowAccount = ConstU64<ESCROW_ACCOUNT>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -86,7 +154,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(1, 10_000_000_000), (2, 10_000_000_000), (3, 9_0000)],
+		balances: vec![
+			(1, 10_000_000_000),
+			(2, 10_000_000_000),
+			(3, 9_0000),
+			(ESCROW_ACCOUNT, 1_000),
+		],
 	}
 	.assimilate_storage(&mut storage)
 	.unwrap();
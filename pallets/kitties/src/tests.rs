@@ -1,8 +1,9 @@
 use std::ops::Add;
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{new_test_ext, Event as TestEvent, KittiesModule, Origin, System, Test};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::{new_test_ext, Balances, Event as TestEvent, KittiesModule, Origin, System, Test};
+use sp_runtime::Permill;
 
 const ACCOUNT_WITH_BALANCE_1: u64 = 1;
 const ACCOUNT_WITH_BALANCE_2: u64 = 2;
@@ -41,17 +42,62 @@ fn create_failed_invalid_kitty_id() {
 }
 
 #[test]
-fn create_failed_own_too_many_kitties() {
+fn create_has_no_ownership_cap() {
 	new_test_ext().execute_with(|| {
 		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		for _ in 0..10 {
+			assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		}
+
+		assert_eq!(KittiesModule::owned_kitties_count(account_id), 10);
+		assert_eq!(KittiesModule::kitties_of(&account_id).len(), 10);
+	});
+}
+
+#[test]
+fn kitties_of_page_walks_all_kitties() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		for _ in 0..5 {
+			assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		}
+		assert_ok!(KittiesModule::create(Origin::signed(ACCOUNT_WITH_BALANCE_2)));
+
+		let mut pages = Vec::new();
+		let mut start_after = None;
+		loop {
+			let page = KittiesModule::kitties_of_page(&account_id, start_after, 2);
+			if page.is_empty() {
+				break
+			}
+			start_after = page.last().copied();
+			pages.push(page);
+		}
+
+		assert_eq!(pages.iter().map(|page| page.len()).collect::<Vec<_>>(), vec![2, 2, 1]);
+		assert_eq!(pages.concat(), KittiesModule::kitties_of(&account_id));
+		assert!(KittiesModule::kitties_of_page(&account_id, None, 0).is_empty());
+	});
+}
+
+#[test]
+fn kitty_details_and_count() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		assert_eq!(KittiesModule::kitty_count(), 0);
+		assert_eq!(KittiesModule::kitty_details(0), None);
+
 		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
 		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
-		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::burn(Origin::signed(account_id), 0));
 
-		assert_noop!(
-			KittiesModule::create(Origin::signed(account_id)),
-			Error::<Test>::OwnTooManyKitties
+		let kitty = KittiesModule::kitties(1).unwrap();
+		assert_eq!(
+			KittiesModule::kitty_details(1),
+			Some(KittyDetails { owner: account_id, genome: kitty.genome(), kitty })
 		);
+		assert_eq!(KittiesModule::kitty_details(0), None);
+		assert_eq!(KittiesModule::kitty_count(), 2);
 	});
 }
 
@@ -103,22 +149,30 @@ fn breed_failed_invalid_kitty_id() {
 }
 
 #[test]
-fn breed_failed_own_too_many_kitties() {
+fn breed_failed_not_owner() {
 	new_test_ext().execute_with(|| {
-		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id_1: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id_2: u64 = ACCOUNT_WITH_BALANCE_2;
 
 		let kitty_id_1 = NextKittyId::<Test>::get();
-		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
-
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
 		let kitty_id_2 = NextKittyId::<Test>::get();
-		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
-
-		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+		let own_kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_2)));
 
 		assert_noop!(
-			KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),
-			Error::<Test>::OwnTooManyKitties
+			KittiesModule::breed(Origin::signed(account_id_2), kitty_id_1, kitty_id_2),
+			Error::<Test>::NotOwner
 		);
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(account_id_2), own_kitty_id, kitty_id_1),
+			Error::<Test>::NotOwner
+		);
+
+		for kitty_id in [kitty_id_1, kitty_id_2, own_kitty_id] {
+			assert_eq!(KittiesModule::ready_to_breed_at(kitty_id), None);
+		}
 	});
 }
 
@@ -168,21 +222,888 @@ fn transfer_failed_not_owner() {
 }
 
 #[test]
-fn transfer_failed_own_too_many_kitties() {
+fn set_price_success() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(account_id), kitty_id, 100));
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), Some(100));
+		System::assert_last_event(TestEvent::KittiesModule(Event::KittyListed(
+			account_id, kitty_id, 100,
+		)));
+	});
+}
+
+#[test]
+fn set_price_failed_not_owner() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(ACCOUNT_WITH_BALANCE_1)));
+
+		assert_noop!(
+			KittiesModule::set_price(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 100),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn clear_price_success() {
 	new_test_ext().execute_with(|| {
-		let account_id_1: u64 = ACCOUNT_WITH_BALANCE_2;
-		let account_id_2: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(account_id), kitty_id, 100));
+
+		assert_ok!(KittiesModule::clear_price(Origin::signed(account_id), kitty_id));
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), None);
+
+		assert_noop!(
+			KittiesModule::clear_price(Origin::signed(account_id), kitty_id),
+			Error::<Test>::NotForSale
+		);
+	});
+}
+
+#[test]
+fn buy_success() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+		let buyer: u64 = ACCOUNT_WITH_BALANCE_2;
+		let kitty_price = mock::KittyPrice::get();
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(seller), kitty_id, 100));
+
+		let seller_free = Balances::free_balance(seller);
+		let buyer_free = Balances::free_balance(buyer);
+
+		assert_ok!(KittiesModule::buy(Origin::signed(buyer), kitty_id, 100));
+
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buyer));
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), None);
+		assert!(KittiesModule::kitties_of(&seller).is_empty());
+		assert_eq!(KittiesModule::kitties_of(&buyer), vec![kitty_id]);
+		assert_eq!(KittiesModule::owned_kitties_count(seller), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(buyer), 1);
+
+		assert_eq!(Balances::free_balance(seller), seller_free + 100 + kitty_price);
+		assert_eq!(Balances::reserved_balance(seller), 0);
+		assert_eq!(Balances::free_balance(buyer), buyer_free - 100 - kitty_price);
+		assert_eq!(Balances::reserved_balance(buyer), kitty_price);
+
+		System::assert_last_event(TestEvent::KittiesModule(Event::KittySold(
+			seller, buyer, kitty_id, 100,
+		)));
+	});
+}
+
+#[test]
+fn buy_failed_not_for_sale() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(ACCOUNT_WITH_BALANCE_1)));
+
+		assert_noop!(
+			KittiesModule::buy(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 100),
+			Error::<Test>::NotForSale
+		);
+	});
+}
+
+#[test]
+fn buy_failed_own_kitty() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(account_id), kitty_id, 100));
+
+		assert_noop!(
+			KittiesModule::buy(Origin::signed(account_id), kitty_id, 100),
+			Error::<Test>::BuyOwnKitty
+		);
+	});
+}
+
+#[test]
+fn buy_failed_price_too_high() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(seller), kitty_id, 100));
+		// The seller raises the price before the buyer's transaction is included.
+		assert_ok!(KittiesModule::set_price(Origin::signed(seller), kitty_id, 200));
+
+		assert_noop!(
+			KittiesModule::buy(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 100),
+			Error::<Test>::PriceTooHigh
+		);
+	});
+}
+
+#[test]
+fn buy_failed_not_enough_balance() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(seller), kitty_id, 100));
+
+		assert_noop!(
+			KittiesModule::buy(Origin::signed(ACCOUNT_WITH_NO_BALANCE), kitty_id, 100),
+			Error::<Test>::NotEnoughBalance
+		);
+	});
+}
+
+#[test]
+fn transfer_clears_price() {
+	new_test_ext().execute_with(|| {
+		let account_id_1: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id_2: u64 = ACCOUNT_WITH_BALANCE_2;
 
 		let kitty_id = NextKittyId::<Test>::get();
 		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(account_id_1), kitty_id, 100));
 
+		assert_ok!(KittiesModule::transfer(Origin::signed(account_id_1), kitty_id, account_id_2));
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), None);
+	});
+}
+
+#[test]
+fn create_auction_success() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(seller), kitty_id, 100));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_eq!(
+			KittiesModule::auctions(kitty_id),
+			Some(Auction { seller, reserve_price: 50, end: 10, best_bid: None })
+		);
+		assert_eq!(KittiesModule::auctions_ending(10).into_inner(), vec![kitty_id]);
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), None);
+		System::assert_last_event(TestEvent::KittiesModule(Event::AuctionStarted(
+			seller, kitty_id, 50, 10,
+		)));
+	});
+}
+
+#[test]
+fn create_auction_failed_invalid_end() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 1),
+			Error::<Test>::InvalidAuctionEnd
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 102),
+			Error::<Test>::InvalidAuctionEnd
+		);
+	});
+}
+
+#[test]
+fn create_auction_failed_too_many_auctions_ending() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		}
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), 0, 50, 10));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), 1, 50, 10));
+
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(seller), 2, 50, 10),
+			Error::<Test>::TooManyAuctionsEnding
+		);
+	});
+}
+
+#[test]
+fn bid_outbid_releases_funds() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+		let first_bidder: u64 = ACCOUNT_WITH_BALANCE_2;
+		let second_bidder: u64 = 3;
+		let kitty_price = mock::KittyPrice::get();
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_ok!(KittiesModule::bid(Origin::signed(first_bidder), kitty_id, 100));
+		assert_eq!(Balances::reserved_balance(first_bidder), 100 + kitty_price);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(second_bidder), kitty_id, 200));
+		assert_eq!(Balances::reserved_balance(first_bidder), 0);
+		assert_eq!(Balances::reserved_balance(second_bidder), 200 + kitty_price);
+		System::assert_last_event(TestEvent::KittiesModule(Event::BidPlaced(
+			second_bidder,
+			kitty_id,
+			200,
+		)));
+	});
+}
+
+#[test]
+fn bid_failed_too_low() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 49),
+			Error::<Test>::BidTooLow
+		);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 100));
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(3), kitty_id, 100),
+			Error::<Test>::BidTooLow
+		);
+	});
+}
+
+#[test]
+fn bid_failed_own_auction() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(seller), kitty_id, 100),
+			Error::<Test>::BidOnOwnAuction
+		);
+	});
+}
+
+#[test]
+fn auction_settles_on_initialize() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+		let buyer: u64 = ACCOUNT_WITH_BALANCE_2;
+		let kitty_price = mock::KittyPrice::get();
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(buyer), kitty_id, 100));
+
+		let seller_free = Balances::free_balance(seller);
+
+		System::set_block_number(10);
+		KittiesModule::on_initialize(10);
+
+		assert_eq!(KittiesModule::auctions(kitty_id), None);
+		assert!(KittiesModule::auctions_ending(10).is_empty());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buyer));
+		assert!(KittiesModule::kitties_of(&seller).is_empty());
+		assert_eq!(KittiesModule::kitties_of(&buyer), vec![kitty_id]);
+		assert_eq!(KittiesModule::owned_kitties_count(seller), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(buyer), 1);
+
+		assert_eq!(Balances::free_balance(seller), seller_free + 100 + kitty_price);
+		assert_eq!(Balances::reserved_balance(seller), 0);
+		assert_eq!(Balances::reserved_balance(buyer), kitty_price);
+
+		System::assert_last_event(TestEvent::KittiesModule(Event::AuctionSettled(
+			seller, buyer, kitty_id, 100,
+		)));
+	});
+}
+
+#[test]
+fn auction_without_bids_keeps_kitty() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		System::set_block_number(10);
+		KittiesModule::on_initialize(10);
+
+		assert_eq!(KittiesModule::auctions(kitty_id), None);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+		System::assert_last_event(TestEvent::KittiesModule(Event::AuctionCancelled(
+			seller, kitty_id,
+		)));
+	});
+}
+
+#[test]
+fn transfer_failed_kitty_on_auction() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_noop!(
+			KittiesModule::transfer(Origin::signed(seller), kitty_id, ACCOUNT_WITH_BALANCE_2),
+			Error::<Test>::KittyOnAuction
+		);
+		assert_noop!(
+			KittiesModule::set_price(Origin::signed(seller), kitty_id, 100),
+			Error::<Test>::KittyOnAuction
+		);
+	});
+}
+
+#[test]
+fn cancel_auction_success() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+
+		assert_ok!(KittiesModule::cancel_auction(Origin::signed(seller), kitty_id));
+		assert_eq!(KittiesModule::auctions(kitty_id), None);
+		assert!(KittiesModule::auctions_ending(10).is_empty());
+	});
+}
+
+#[test]
+fn cancel_auction_failed_has_bids() {
+	new_test_ext().execute_with(|| {
+		let seller: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(seller)));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(seller), kitty_id, 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id, 100));
+
+		assert_noop!(
+			KittiesModule::cancel_auction(Origin::signed(seller), kitty_id),
+			Error::<Test>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn burn_success() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		let free_before = Balances::free_balance(account_id);
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::set_price(Origin::signed(account_id), kitty_id, 100));
+		assert_eq!(Balances::reserved_balance(account_id), mock::KittyPrice::get());
+
+		assert_ok!(KittiesModule::burn(Origin::signed(account_id), kitty_id));
+
+		assert_eq!(KittiesModule::kitties(kitty_id), None);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), None);
+		assert_eq!(KittiesModule::kitty_prices(kitty_id), None);
+		assert!(KittiesModule::kitties_of(&account_id).is_empty());
+		assert_eq!(KittiesModule::owned_kitties_count(account_id), 0);
+
+		assert_eq!(Balances::free_balance(account_id), free_before);
+		assert_eq!(Balances::reserved_balance(account_id), 0);
+		System::assert_last_event(TestEvent::KittiesModule(Event::KittyBurned(
+			account_id, kitty_id,
+		)));
+	});
+}
+
+#[test]
+fn burn_frees_owner_slot() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+
+		assert_ok!(KittiesModule::burn(Origin::signed(account_id), kitty_id));
+		assert_eq!(KittiesModule::owned_kitties_count(account_id), 2);
+		assert!(!KittiesModule::kitties_of(&account_id).contains(&kitty_id));
+	});
+}
+
+#[test]
+fn burn_failed_not_owner() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(ACCOUNT_WITH_BALANCE_1)));
+
+		assert_noop!(
+			KittiesModule::burn(Origin::signed(ACCOUNT_WITH_BALANCE_2), kitty_id),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn burn_failed_invalid_kitty_id() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = NextKittyId::<Test>::get();
+
+		assert_noop!(
+			KittiesModule::burn(Origin::signed(ACCOUNT_WITH_BALANCE_1), kitty_id),
+			Error::<Test>::InvalidKittyId
+		);
+	});
+}
+
+#[test]
+fn breed_records_lineage() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+
+		System::set_block_number(5);
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+
+		let parent = KittiesModule::kitties(kitty_id_1).unwrap();
+		assert_eq!(parent.generation, 0);
+		assert_eq!(parent.parents, None);
+		assert_eq!(parent.birth_block, 1);
+
+		let child = KittiesModule::kitties(child_id).unwrap();
+		assert_eq!(child.generation, 1);
+		assert_eq!(child.parents, Some((kitty_id_1, kitty_id_2)));
+		assert_eq!(child.birth_block, 5);
+		assert_eq!(KittiesModule::genome(child_id), Some(child.genome()));
+	});
+}
+
+#[test]
+fn genome_decodes_dna() {
+	let mut dna = [0u8; 16];
+	dna[0] = 9;
+	dna[1] = 4;
+	dna[2] = 3;
+	dna[3] = 250;
+
+	assert_eq!(
+		Genome::from_dna(&dna, 2),
+		Genome {
+			color: genome::Color::White,
+			pattern: genome::Pattern::Calico,
+			eye_shape: genome::EyeShape::Sleepy,
+			rarity: genome::Rarity::Rare,
+			generation: 2,
+		}
+	);
+}
+
+#[test]
+fn migrate_v1_wraps_old_kitties() {
+	use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<KittiesModule>();
+		frame_support::storage::unhashed::put(&Kitties::<Test>::hashed_key_for(0), &[7u8; 16]);
+
+		migrations::v1::migrate::<Test>();
+
+		assert_eq!(
+			KittiesModule::kitties(0),
+			Some(Kitty { dna: [7u8; 16], generation: 0, parents: None, birth_block: 0 })
+		);
+		assert_eq!(KittiesModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migrate_v2_moves_all_kitties() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+		StorageVersion::new(1).put::<KittiesModule>();
+		put_storage_value(
+			b"KittiesModule",
+			b"AllKitties",
+			&Blake2_128Concat::hash(&account_id.encode()),
+			vec![0u32, 2],
+		);
+
+		migrations::v2::migrate::<Test>();
+
+		assert_eq!(KittiesModule::owned_kitties_count(account_id), 2);
+		assert!(OwnedKitties::<Test>::contains_key(account_id, 0));
+		assert!(OwnedKitties::<Test>::contains_key(account_id, 2));
+		assert!(!OwnedKitties::<Test>::contains_key(account_id, 1));
+		assert_eq!(KittiesModule::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn breed_failed_on_cooldown() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+		assert_ok!(KittiesModule::burn(Origin::signed(account_id), child_id));
+
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2),
+			Error::<Test>::KittyOnCooldown
+		);
+
+		System::set_block_number(11);
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+	});
+}
+
+#[test]
+fn breed_cooldown_grows_with_generation() {
+	new_test_ext().execute_with(|| {
+		let account_id_1: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id_2: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id_1), kitty_id_1, kitty_id_2));
+		assert_eq!(KittiesModule::ready_to_breed_at(kitty_id_1), Some(11));
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(account_id_1), child_id, account_id_2));
+		let stranger_id = NextKittyId::<Test>::get();
 		assert_ok!(KittiesModule::create(Origin::signed(account_id_2)));
-		assert_ok!(KittiesModule::create(Origin::signed(account_id_2)));
-		assert_ok!(KittiesModule::create(Origin::signed(account_id_2)));
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id_2), child_id, stranger_id));
+
+		assert_eq!(KittiesModule::ready_to_breed_at(child_id), Some(21));
+		assert_eq!(KittiesModule::ready_to_breed_at(stranger_id), Some(11));
+	});
+}
+
+#[test]
+fn breed_failed_with_parent() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
 
 		assert_noop!(
-			KittiesModule::transfer(Origin::signed(account_id_1), kitty_id, account_id_2),
-			Error::<Test>::OwnTooManyKitties
+			KittiesModule::breed(Origin::signed(account_id), child_id, kitty_id_1),
+			Error::<Test>::BreedWithParent
+		);
+	});
+}
+
+#[test]
+fn breed_failed_with_sibling() {
+	new_test_ext().execute_with(|| {
+		let account_id_1: u64 = ACCOUNT_WITH_BALANCE_1;
+		let account_id_2: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id_1)));
+
+		let first_child = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id_1), kitty_id_1, kitty_id_2));
+		assert_ok!(KittiesModule::transfer(
+			Origin::signed(account_id_1),
+			first_child,
+			account_id_2
+		));
+
+		System::set_block_number(11);
+		let second_child = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id_1), kitty_id_1, kitty_id_2));
+		assert_ok!(KittiesModule::transfer(
+			Origin::signed(account_id_1),
+			second_child,
+			account_id_2
+		));
+
+		assert_noop!(
+			KittiesModule::breed(Origin::signed(account_id_2), first_child, second_child),
+			Error::<Test>::BreedWithSibling
+		);
+	});
+}
+
+#[test]
+fn breed_mutation_flips_one_bit() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		Kitties::<Test>::mutate(kitty_id_1, |kitty| kitty.as_mut().unwrap().dna = [0u8; 16]);
+		Kitties::<Test>::mutate(kitty_id_2, |kitty| kitty.as_mut().unwrap().dna = [0u8; 16]);
+
+		mock::MutationChance::set(Permill::one());
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+
+		let child = KittiesModule::kitties(child_id).unwrap();
+		assert_eq!(child.dna.iter().map(|byte| byte.count_ones()).sum::<u32>(), 1);
+	});
+}
+
+#[test]
+fn breed_without_mutation_keeps_dna() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id_1 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		let kitty_id_2 = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(account_id)));
+		Kitties::<Test>::mutate(kitty_id_1, |kitty| kitty.as_mut().unwrap().dna = [0u8; 16]);
+		Kitties::<Test>::mutate(kitty_id_2, |kitty| kitty.as_mut().unwrap().dna = [0u8; 16]);
+
+		mock::MutationChance::set(Permill::zero());
+		let child_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::breed(Origin::signed(account_id), kitty_id_1, kitty_id_2));
+
+		assert_eq!(KittiesModule::kitties(child_id).unwrap().dna, [0u8; 16]);
+	});
+}
+
+#[test]
+fn transfer_from_with_approval() {
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+		let spender: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(spender), owner, spender, kitty_id),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(KittiesModule::approve(Origin::signed(owner), kitty_id, Some(spender)));
+		assert_eq!(KittiesModule::kitty_approvals(kitty_id), Some(spender));
+		System::assert_last_event(TestEvent::KittiesModule(Event::Approval(
+			owner,
+			Some(spender),
+			kitty_id,
+		)));
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(spender), owner, spender, kitty_id));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(spender));
+		assert_eq!(KittiesModule::kitty_approvals(kitty_id), None);
+		assert_eq!(Balances::reserved_balance(owner), 0);
+		assert_eq!(Balances::reserved_balance(spender), mock::KittyPrice::get());
+		System::assert_last_event(TestEvent::KittiesModule(Event::KittyTransferred(
+			owner, spender, kitty_id,
+		)));
+	});
+}
+
+#[test]
+fn transfer_clears_approval() {
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+		let spender: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+		assert_ok!(KittiesModule::approve(Origin::signed(owner), kitty_id, Some(spender)));
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(owner), kitty_id, 3));
+		assert_eq!(KittiesModule::kitty_approvals(kitty_id), None);
+
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(spender), 3, spender, kitty_id),
+			Error::<Test>::NotApproved
 		);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn operator_can_approve_and_transfer() {
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+		let operator: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+
+		assert_noop!(
+			KittiesModule::approve(Origin::signed(operator), kitty_id, Some(3)),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(owner), operator, true));
+		assert!(OperatorApprovals::<Test>::contains_key(owner, operator));
+		System::assert_last_event(TestEvent::KittiesModule(Event::ApprovalForAll(
+			owner, operator, true,
+		)));
+
+		assert_ok!(KittiesModule::approve(Origin::signed(operator), kitty_id, Some(3)));
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(operator), owner, 3, kitty_id));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(3));
+
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(owner), operator, false));
+		assert!(!OperatorApprovals::<Test>::contains_key(owner, operator));
+	});
+}
+
+#[test]
+fn approve_failed() {
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_noop!(
+			KittiesModule::approve(Origin::signed(owner), kitty_id, Some(2)),
+			Error::<Test>::InvalidKittyId
+		);
+
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+		assert_noop!(
+			KittiesModule::approve(Origin::signed(owner), kitty_id, Some(owner)),
+			Error::<Test>::ApproveToSelf
+		);
+		assert_noop!(
+			KittiesModule::set_approval_for_all(Origin::signed(owner), owner, true),
+			Error::<Test>::ApproveToSelf
+		);
+	});
+}
+
+#[test]
+fn escrow_moves_kitty_through_nonfungibles() {
+	use mock::{Escrow, ESCROW_ACCOUNT};
+
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+		let receiver: u64 = ACCOUNT_WITH_BALANCE_2;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+
+		assert_noop!(
+			Escrow::deposit(Origin::signed(receiver), kitty_id),
+			mock::pallet_escrow::Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Escrow::deposit(Origin::signed(owner), kitty_id));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(ESCROW_ACCOUNT));
+		assert_eq!(Balances::reserved_balance(ESCROW_ACCOUNT), mock::KittyPrice::get());
+
+		assert_ok!(Escrow::release(Origin::signed(owner), kitty_id, receiver));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(receiver));
+		assert_eq!(KittiesModule::kitties_of(&receiver), vec![kitty_id]);
+		assert_eq!(Balances::reserved_balance(ESCROW_ACCOUNT), 0);
+	});
+}
+
+#[test]
+fn nonfungibles_inspect() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		let kitty_id = NextKittyId::<Test>::get();
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &kitty_id), None);
+
+		assert_ok!(KittiesModule::create(Origin::signed(owner)));
+		let dna = KittiesModule::kitties(kitty_id).unwrap().dna;
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &kitty_id), Some(owner));
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&(), &kitty_id, b"dna"),
+			Some(dna.to_vec())
+		);
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(owner), kitty_id, 100, 10));
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+	});
+}
+
+#[test]
+fn nonfungibles_mint_and_burn() {
+	use frame_support::traits::tokens::{
+		nonfungible,
+		nonfungibles::{Create, Mutate},
+	};
+	use sp_runtime::TokenError;
+
+	new_test_ext().execute_with(|| {
+		let owner: u64 = ACCOUNT_WITH_BALANCE_1;
+
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&(), &5, &owner));
+		assert_eq!(KittiesModule::kitty_owner(5), Some(owner));
+		assert_eq!(KittiesModule::next_kitty_id(), 6);
+		assert_eq!(Balances::reserved_balance(owner), mock::KittyPrice::get());
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&(), &5, &owner),
+			Error::<Test>::InvalidKittyId
+		);
+
+		assert_noop!(
+			<KittiesModule as nonfungible::Mutate<u64>>::burn(&5, Some(&2)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(<KittiesModule as nonfungible::Mutate<u64>>::burn(&5, None));
+		assert_eq!(KittiesModule::kitties(5), None);
+		assert_eq!(Balances::reserved_balance(owner), 0);
+
+		assert_noop!(
+			<KittiesModule as Create<u64>>::create_collection(&(), &owner, &owner),
+			TokenError::Unsupported
+		);
+	});
+}
@@ -0,0 +1,348 @@
+//! Weights for pallet_kitties
+//!
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS. The storage comments and database
+//! read/write counts follow what each call touches; the base and per-item times are rough
+//! guesses. Regenerate this file with `scripts/benchmark_weights.sh` before relying on it for
+//! fees.
+
+// Command to regenerate:
+// ./target/release/node-template
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_kitties
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --template=.maintain/frame-weight-template.hbs
+// --output=pallets/kitties/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn breed() -> Weight;
+	fn transfer() -> Weight;
+	fn set_price() -> Weight;
+	fn clear_price() -> Weight;
+	fn buy() -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn cancel_auction() -> Weight;
+	fn burn() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
+	fn on_initialize(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: KittiesModule NextKittyId (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:1)
+	fn create() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:3 w:1)
+	// Storage: KittiesModule ReadyToBreedAt (r:2 w:2)
+	// Storage: KittiesModule NextKittyId (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:1)
+	fn breed() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn transfer() -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	fn set_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule KittyPrices (r:1 w:1)
+	fn clear_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn buy() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	fn create_auction() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	fn cancel_auction() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule ReadyToBreedAt (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn burn() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn transfer_from() -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: KittiesModule NextKittyId (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:1)
+	fn create() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:3 w:1)
+	// Storage: KittiesModule ReadyToBreedAt (r:2 w:2)
+	// Storage: KittiesModule NextKittyId (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:1)
+	fn breed() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn transfer() -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	fn set_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule KittyPrices (r:1 w:1)
+	fn clear_price() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn buy() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	fn create_auction() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	fn cancel_auction() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	// Storage: KittiesModule OwnedKittiesCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule ReadyToBreedAt (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn burn() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:0)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule KittyOwner (r:1 w:1)
+	// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn transfer_from() -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule OwnedKittiesCount (r:2 w:2)
+	// Storage: KittiesModule KittyOwner (r:0 w:1)
+	// Storage: KittiesModule KittyPrices (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule OwnedKitties (r:0 w:2)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
+}
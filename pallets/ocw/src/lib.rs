@@ -14,132 +14,272 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod source;
+pub use source::DataSource;
+
 use sp_runtime::{
 	offchain::storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
+	offchain::storage_lock::{BlockAndTime, StorageLock, StorageLockGuard},
 	offchain::{http, Duration},
 	traits::Zero,
 };
 
-use serde::{Deserialize, Deserializer};
-
-use frame_system::{
-    offchain::{
-        AppCrypto, CreateSignedTransaction, SendSignedTransaction, SubmitTransaction,
-        Signer,
-    },
+use codec::Encode;
+use frame_system::offchain::{
+	AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+	SignedPayload, Signer, SigningTypes,
 };
 use sp_core::crypto::KeyTypeId;
+use sp_std::vec::Vec;
 
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ocwd");
 pub mod crypto {
-    use super::KEY_TYPE;
-    use sp_core::sr25519::Signature as Sr25519Signature;
-    use sp_runtime::{
-        app_crypto::{app_crypto, sr25519},
-        traits::Verify,
-        MultiSignature, MultiSigner,
-    };
-    app_crypto!(sr25519, KEY_TYPE);
-
-    pub struct OcwAuthId;
-
-    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OcwAuthId {
-        type RuntimeAppPublic = Public;
-        type GenericSignature = sp_core::sr25519::Signature;
-        type GenericPublic = sp_core::sr25519::Public;
-    }
-
-    impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
-        for OcwAuthId
-        {
-            type RuntimeAppPublic = Public;
-            type GenericSignature = sp_core::sr25519::Signature;
-            type GenericPublic = sp_core::sr25519::Public;
-        }
-}
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OcwAuthId;
 
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OcwAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for OcwAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
 
 const ONCHAIN_TX_KEY: &[u8] = b"ocw::storage::tx";
 
+/// Persistent local storage key of the lock held while a worker runs.
+const LOCK_KEY: &[u8] = b"ocw::lock";
+/// Persistent local storage key of the last block a worker ran for.
+pub const LAST_PROCESSED_KEY: &[u8] = b"ocw::last-processed-block";
+const LOCK_BLOCK_EXPIRATION: u32 = 3;
+/// Longer than `source::MAX_DEADLINE_MS`, as the worker extends the lock before each source.
+const LOCK_TIMEOUT_EXPIRATION_MS: u64 = 20_000;
+/// Blocks after `DataPayload::block_number` an unsigned transaction carrying it stays valid.
+const UNSIGNED_TX_LONGEVITY: u32 = 3;
+
+/// Persistent local storage key of a SCALE encoded `Vec<DataSource>`. When set, e.g. through the
+/// `offchain_localStorageSet` RPC, the worker queries these sources instead of `DataSources`.
+pub const LOCAL_DATA_SOURCES_KEY: &[u8] = b"ocw::data-sources";
+
+/// `ONCHAIN_TX_KEY/<block_number>`, with the block number SCALE encoded.
+pub fn derive_key<BlockNumber: Encode>(block_number: BlockNumber) -> Vec<u8> {
+	block_number.using_encoded(|encoded_bn| {
+		ONCHAIN_TX_KEY
+			.iter()
+			.chain(b"/".iter())
+			.chain(encoded_bn)
+			.copied()
+			.collect::<Vec<u8>>()
+	})
+}
+
+/// `ONCHAIN_TX_KEY/<parent_hash>/<extrinsic_hash>`, the offchain indexing key of the
+/// `IndexingData` written by the extrinsic hashing to `extrinsic_hash` in a block built on
+/// `parent_hash`. Blocks of competing forks only share a key when they include the same
+/// extrinsic, which indexes the same data in each of them.
+pub fn indexing_key<Hash: Encode>(parent_hash: Hash, extrinsic_hash: Hash) -> Vec<u8> {
+	let mut key = ONCHAIN_TX_KEY.to_vec();
+	for part in [parent_hash.encode(), extrinsic_hash.encode()] {
+		key.push(b'/');
+		key.extend(part);
+	}
+	key
+}
+
+/// The median of `values`, or the mean of the two middle values for an even count.
+pub fn median(values: &mut [u32]) -> Option<u32> {
+	if values.is_empty() {
+		return None
+	}
+	values.sort_unstable();
+
+	let mid = values.len() / 2;
+	if values.len() % 2 == 0 {
+		Some(((values[mid - 1] as u64 + values[mid] as u64) / 2) as u32)
+	} else {
+		Some(values[mid])
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	// use frame_support::inherent::Vec;
-	use sp_std::vec::Vec;
-    use sp_std::vec;
 	use frame_support::{
 		pallet_prelude::*,
 		sp_runtime::offchain::storage::{StorageRetrievalError, StorageValueRef},
 	};
-	use frame_system::{pallet_prelude::*, offchain::SendTransactionTypes};
+	use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
 	use sp_io::offchain_index;
+	use sp_runtime::traits::{Hash, IdentifyAccount, Saturating};
+	use sp_std::vec::Vec;
 
-	#[derive(Deserialize, Encode, Decode)]
-	struct GithubInfo {
-		#[serde(deserialize_with = "de_string_to_bytes")]
-		login: Vec<u8>,
-		#[serde(deserialize_with = "de_string_to_bytes")]
-		blog: Vec<u8>,
-		public_repos: u32,
+	/// The label and number `submit_data_unsigned_with_signed_payload` indexes off chain under
+	/// `indexing_key`.
+	#[derive(Debug, Encode, Decode, Default, Clone, PartialEq, Eq)]
+	pub struct IndexingData(pub Vec<u8>, pub u64);
+
+	/// Data an authority signs off chain and submits in an unsigned transaction.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct DataPayload<Public, BlockNumber> {
+		pub block_number: BlockNumber,
+		pub n: u64,
+		pub public: Public,
 	}
 
-	use core::{convert::TryInto, fmt};
-    impl fmt::Debug for GithubInfo {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(
-                f,
-                "{{ login: {}, blog: {}, public_repos: {} }}",
-                sp_std::str::from_utf8(&self.login).map_err(|_| fmt::Error)?,
-                sp_std::str::from_utf8(&self.blog).map_err(|_| fmt::Error)?,
-                &self.public_repos
-                )
-        }
-    }
-
-	#[derive(Debug, Deserialize, Encode, Decode, Default)]
-	struct IndexingData(Vec<u8>, u64);
-	
-
-	pub fn de_string_to_bytes<'de, D>(de: D) -> Result<Vec<u8>, D::Error>
-	where
-	D: Deserializer<'de>,
-	{
-		let s: &str = Deserialize::deserialize(de)?;
-		Ok(s.as_bytes().to_vec())
+	impl<T: SigningTypes> SignedPayload<T> for DataPayload<T::Public, T::BlockNumber> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
 	}
 
-
 	#[pallet::config]
-	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> + SendTransactionTypes<Call<Self>>{
+	pub trait Config:
+		frame_system::Config
+		+ CreateSignedTransaction<Call<Self>>
+		+ SendTransactionTypes<Call<Self>>
+	{
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Most endpoints in `DataSources`.
+		#[pallet::constant]
+		type MaxDataSources: Get<u32>;
+		/// Recent prices kept per submitter; the oldest is dropped once full.
+		#[pallet::constant]
+		type MaxPricesPerAccount: Get<u32>;
+		/// Blocks a price counts towards the median after it was submitted.
+		#[pallet::constant]
+		type MaxPriceAge: Get<Self::BlockNumber>;
+		/// Most accounts in `Authorities`, and so most accounts with prices in `Submissions`.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+		/// Priority of unsigned transactions carrying a signed payload.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// The origin that may add and remove `Authorities`.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
-
 	#[pallet::storage]
 	#[pallet::getter(fn something)]
 	pub type Something<T> = StorageValue<_, u32>;
 
+	/// The most recent prices, in cents, submitted by each account and the blocks they landed in.
+	#[pallet::storage]
+	pub type Submissions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(u32, T::BlockNumber), T::MaxPricesPerAccount>,
+		ValueQuery,
+	>;
+
+	/// The median of all prices in `Submissions`, in cents.
+	#[pallet::storage]
+	#[pallet::getter(fn price)]
+	pub type Price<T> = StorageValue<_, u32>;
+
+	/// Accounts whose `ocwd` keys may submit prices and sign payloads for unsigned transactions.
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub type Authorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAuthorities>, ValueQuery>;
+
+	/// JSON endpoints the worker reads prices from. It submits the median of the endpoints that
+	/// answered.
+	#[pallet::storage]
+	#[pallet::getter(fn data_sources)]
+	pub type DataSources<T: Config> =
+		StorageValue<_, BoundedVec<DataSource, T::MaxDataSources>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub authorities: Vec<T::AccountId>,
+		pub data_sources: Vec<DataSource>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { authorities: Vec::new(), data_sources: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let authorities =
+				BoundedVec::<_, T::MaxAuthorities>::try_from(self.authorities.clone())
+					.expect("genesis authorities exceed MaxAuthorities");
+			Authorities::<T>::put(authorities);
+
+			assert!(
+				self.data_sources
+					.iter()
+					.all(|source| source.deadline_ms <= source::MAX_DEADLINE_MS),
+				"genesis data source deadline exceeds MAX_DEADLINE_MS"
+			);
+			let data_sources =
+				BoundedVec::<_, T::MaxDataSources>::try_from(self.data_sources.clone())
+					.expect("genesis data sources exceed MaxDataSources");
+			DataSources::<T>::put(data_sources);
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		SomethingStored(u32, T::AccountId),
+		/// A price was submitted. [who, price]
+		PriceSubmitted(T::AccountId, u32),
+		/// The published median price changed. [price]
+		PriceUpdated(u32),
+		/// No price younger than `MaxPriceAge` is left, so the published price was cleared.
+		PriceCleared,
+		/// An account was allowed to submit. [who]
+		AuthorityAdded(T::AccountId),
+		/// An account was no longer allowed to submit and its prices were dropped. [who]
+		AuthorityRemoved(T::AccountId),
+		/// `DataSources` was replaced. [count]
+		DataSourcesUpdated(u32),
 	}
 
-
 	#[pallet::error]
 	pub enum Error<T> {
 		NoneValue,
 		StorageOverflow,
+		/// The submitter is not one of the `Authorities`.
+		NotAuthority,
+		/// The account already is one of the `Authorities`.
+		AlreadyAuthority,
+		/// `Authorities` already holds `MaxAuthorities` accounts.
+		TooManyAuthorities,
+		/// More than `MaxDataSources` endpoints were given.
+		TooManyDataSources,
+		/// A data source waits longer than `source::MAX_DEADLINE_MS` for its response.
+		DeadlineTooLong,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -171,33 +311,109 @@ pub mod pallet {
 			}
 		}
 
+		/// Record a price in cents and republish the median of all recent submissions. Only
+		/// `Authorities` may submit.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(
+				2 + 2 * T::MaxAuthorities::get() as u64,
+				2 + T::MaxAuthorities::get() as u64,
+			)
+		)]
+		pub fn submit_price(origin: OriginFor<T>, price: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Authorities::<T>::get().contains(&who), Error::<T>::NotAuthority);
 
-		#[pallet::weight(0)]
-        pub fn submit_data(origin: OriginFor<T>, payload: Vec<u8>) -> DispatchResultWithPostInfo {
+			Self::add_price(who, price)
+		}
+
+		/// Allow `who` to submit prices and sign unsigned payloads.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Authorities::<T>::try_mutate(|authorities| {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyAuthority);
+				authorities.try_push(who.clone()).map_err(|_| Error::<T>::TooManyAuthorities)
+			})?;
+
+			Self::deposit_event(Event::AuthorityAdded(who));
+
+			Ok(())
+		}
+
+		/// Revoke `who` and drop its submitted prices from the median.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2 + T::MaxAuthorities::get() as u64, 3)
+		)]
+		pub fn remove_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Authorities::<T>::try_mutate(|authorities| {
+				let index = authorities
+					.iter()
+					.position(|authority| *authority == who)
+					.ok_or(Error::<T>::NotAuthority)?;
+				authorities.remove(index);
+				Ok::<(), Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::AuthorityRemoved(who.clone()));
+
+			if Submissions::<T>::contains_key(&who) {
+				Submissions::<T>::remove(&who);
+				Self::publish_median();
+			}
 
-            let _who = ensure_signed(origin)?;
+			Ok(())
+		}
+
+		/// Replace the endpoints the worker queries for prices. Each may wait at most
+		/// `source::MAX_DEADLINE_MS` for its response.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_data_sources(origin: OriginFor<T>, sources: Vec<DataSource>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				sources.iter().all(|source| source.deadline_ms <= source::MAX_DEADLINE_MS),
+				Error::<T>::DeadlineTooLong
+			);
 
-            log::info!("in submit_data call: {:?}", payload);
+			let count = sources.len() as u32;
+			let sources = BoundedVec::<_, T::MaxDataSources>::try_from(sources)
+				.map_err(|_| Error::<T>::TooManyDataSources)?;
+			DataSources::<T>::put(sources);
 
-            Ok(().into())
-        }
+			Self::deposit_event(Event::DataSourcesUpdated(count));
 
+			Ok(())
+		}
+
+		/// Index `payload.n` off chain under the `indexing_key` of this extrinsic. Only valid with
+		/// a `signature` of `payload` by one of the `Authorities`, which `validate_unsigned`
+		/// checks.
 		#[pallet::weight(0)]
-		pub fn submit_data_unsigned(origin: OriginFor<T>, n: u64) -> DispatchResult {
+		pub fn submit_data_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			payload: DataPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
 			ensure_none(origin)?;
 
-            
-			// let key = Self::derive_key(frame_system::Module::<T>::block_number());
-			// let key = b"test_key".to_vec();
-			let key = b"testKey".to_vec();
+			let n = payload.n;
+
+			let extrinsic_index = frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default();
+			let key = indexing_key(
+				frame_system::Pallet::<T>::parent_hash(),
+				T::Hashing::hash(&frame_system::Pallet::<T>::extrinsic_data(extrinsic_index)),
+			);
 			let data = IndexingData(b"submit_number_unsigned".to_vec(), n);
 			offchain_index::set(&key, &data.encode());
-			log::info!("in submit_data_unsigned, value: {:?}", n);
+			log::info!("in submit_data_unsigned_with_signed_payload, value: {:?}", n);
 
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
-
 	}
 
 	#[pallet::hooks]
@@ -205,32 +421,33 @@ pub mod pallet {
 		fn offchain_worker(block_number: T::BlockNumber) {
 			log::info!("Hello from offchain workers!: {:?}", block_number);
 
-			// unsigned transaction + write to offchain storage
-			let value: u64 = 42;
-
-            let call = Call::submit_data_unsigned { n: value };
-
-            _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
-                .map_err(|_| {
-                    log::error!("Failed in offchain_unsigned_tx");
-                });
-
-			// transaction
-			// let payload: Vec<u8> = vec![1,2,3,4,5,6,7,8];
-            // _ = Self::send_signed_tx(payload);
-			
-
-			// offchain http
-			// if let Ok(info) = Self::fetch_github_info() {
-            //     log::info!("Github Info: {:?}", info);
-            // } else {
-            //     log::info!("Error while fetch github info!");
-            // }
-			
-			// offchain storage
+			let mut lock = Self::worker_lock();
+			let mut guard = match lock.try_lock() {
+				Ok(guard) => guard,
+				Err(_) => {
+					log::info!("Another offchain worker is running, skipping {:?}", block_number);
+					return
+				},
+			};
+			if let Err(e) = Self::claim_block(block_number) {
+				log::info!("Skipping {:?}: {}", block_number, e);
+				return
+			}
+
+			// unsigned transaction with signed payload + write to offchain storage
+			if let Err(e) = Self::send_unsigned_data(block_number, 42) {
+				log::warn!("Data not submitted: {}", e);
+			}
+
+			// offchain http + signed transaction
+			if let Err(e) = Self::fetch_and_submit_price(&mut guard) {
+				log::warn!("Price not submitted: {}", e);
+			}
+
+			// offchain storage, safe to write directly while holding the lock
 			if block_number % 2u32.into() != Zero::zero() {
 				// odd
-				let key = Self::derive_key(block_number);
+				let key = derive_key(block_number);
 				let val_ref = StorageValueRef::persistent(&key);
 
 				// get a local random value
@@ -239,33 +456,13 @@ pub mod pallet {
 				// get a local timestamp
 				let timestamp_u64 = sp_io::offchain::timestamp().unix_millis();
 
-				// combine to a tuple and print it
+				// combine to a tuple and store it
 				let value = (random_slice, timestamp_u64);
 				log::info!("in odd block, value to write: {:?}", value);
-
-				struct StateError;
-
-				// write or mutate tuple content to key
-				// val_ref.set(&value);
-
-				let res = val_ref.mutate(|val: Result<Option<([u8; 32], u64)>, StorageRetrievalError>| -> Result<_, StateError> {
-					match val {
-						Ok(Some(_)) => Ok(value),
-						_ => Ok(value),
-					}
-				});
-
-				match res {
-					Ok(value) => {
-						log::info!("in odd block, mutate successfully: {:?}", value);
-					},
-					Err(MutateStorageError::ValueFunctionFailed(_)) => (),
-					Err(MutateStorageError::ConcurrentModification(_)) => (),
-				};
-
+				val_ref.set(&value);
 			} else {
 				// even
-				let key = Self::derive_key(block_number - 1u32.into());
+				let key = derive_key(block_number - 1u32.into());
 				// ? Why need mut
 				let mut val_ref = StorageValueRef::persistent(&key);
 
@@ -283,86 +480,239 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// The lock serializing workers of this node. It expires `LOCK_BLOCK_EXPIRATION` blocks
+		/// and `LOCK_TIMEOUT_EXPIRATION_MS` after it was taken, so a crashed worker cannot hold
+		/// it forever.
+		pub(crate) fn worker_lock() -> StorageLock<'static, BlockAndTime<frame_system::Pallet<T>>> {
+			StorageLock::with_block_and_time_deadline(
+				LOCK_KEY,
+				LOCK_BLOCK_EXPIRATION,
+				Duration::from_millis(LOCK_TIMEOUT_EXPIRATION_MS),
+			)
+		}
+
+		/// Advance the last processed block marker to `block_number`, failing if that block or a
+		/// later one was already processed.
+		fn claim_block(block_number: T::BlockNumber) -> Result<(), &'static str> {
+			let marker = StorageValueRef::persistent(LAST_PROCESSED_KEY);
+			let res =
+				marker.mutate(|last: Result<Option<T::BlockNumber>, StorageRetrievalError>| {
+					match last {
+						Ok(Some(last)) if last >= block_number => Err(()),
+						_ => Ok(block_number),
+					}
+				});
+
+			match res {
+				Ok(_) => Ok(()),
+				Err(MutateStorageError::ValueFunctionFailed(())) => Err("already processed"),
+				Err(MutateStorageError::ConcurrentModification(_)) =>
+					Err("claimed by another worker"),
+			}
+		}
+
+		/// Append `price` to the ring of `who` and publish the new median.
+		fn add_price(who: T::AccountId, price: u32) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			Self::drop_stale_submitters(now);
+
+			Submissions::<T>::mutate(&who, |prices| {
+				prices.retain(|(_, at)| Self::is_fresh(*at, now));
+				if prices.len() as u32 >= T::MaxPricesPerAccount::get() && !prices.is_empty() {
+					prices.remove(0);
+				}
+				let _ = prices.try_push((price, now));
+			});
+
+			Self::deposit_event(Event::PriceSubmitted(who, price));
+			Self::publish_median();
+
+			Ok(())
+		}
+
+		/// Whether a price submitted at `at` still counts towards the median at `now`.
+		fn is_fresh(at: T::BlockNumber, now: T::BlockNumber) -> bool {
+			now.saturating_sub(at) <= T::MaxPriceAge::get()
+		}
+
+		/// Remove the submissions of every account whose newest price is older than
+		/// `MaxPriceAge`.
+		fn drop_stale_submitters(now: T::BlockNumber) {
+			let stale = Submissions::<T>::iter()
+				.filter(|(_, prices)| {
+					prices.last().map_or(true, |(_, at)| !Self::is_fresh(*at, now))
+				})
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			for who in stale {
+				Submissions::<T>::remove(&who);
+			}
+		}
+
+		/// Recompute the median of all submissions younger than `MaxPriceAge`. Without any, the
+		/// published price is cleared.
+		fn publish_median() {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut prices = Submissions::<T>::iter_values()
+				.flat_map(|prices| prices.into_iter())
+				.filter(|(_, at)| Self::is_fresh(*at, now))
+				.map(|(price, _)| price)
+				.collect::<Vec<_>>();
+			match median(&mut prices) {
+				Some(median) if Price::<T>::get() != Some(median) => {
+					Price::<T>::put(median);
+					Self::deposit_event(Event::PriceUpdated(median));
+				},
+				Some(_) => {},
+				None =>
+					if Price::<T>::take().is_some() {
+						Self::deposit_event(Event::PriceCleared);
+					},
+			}
+		}
+
+		/// The sources in `LOCAL_DATA_SOURCES_KEY` if set, otherwise `DataSources`.
+		pub(crate) fn active_data_sources() -> Vec<DataSource> {
+			match StorageValueRef::persistent(LOCAL_DATA_SOURCES_KEY).get::<Vec<DataSource>>() {
+				Ok(Some(sources)) => sources,
+				Ok(None) => DataSources::<T>::get().into_inner(),
+				Err(_) => {
+					log::warn!("Undecodable data sources in local storage, using on-chain ones");
+					DataSources::<T>::get().into_inner()
+				},
+			}
+		}
 
-		// #[deny(clippy::clone_double_ref)]
-		fn derive_key(block_number: T::BlockNumber) -> Vec<u8> {
-			block_number.using_encoded(|encoded_bn| {
-			  ONCHAIN_TX_KEY.clone().into_iter()
-				.chain(b"/".into_iter())
-				.chain(encoded_bn)
-				.copied()
-				.collect::<Vec<u8>>()
+		/// Query every active data source and submit the median of their answers with each local
+		/// `ocwd` key. The worker lock held by `guard` is extended before each source, so it does
+		/// not expire while the sources are queried one after another.
+		pub(crate) fn fetch_and_submit_price(
+			guard: &mut StorageLockGuard<'_, '_, BlockAndTime<frame_system::Pallet<T>>>,
+		) -> Result<(), &'static str> {
+			let mut prices = Vec::new();
+			for source in Self::active_data_sources() {
+				guard.extend_lock().map_err(|_| "Lost the worker lock")?;
+				match Self::fetch_price(&source) {
+					Ok(price) => prices.push(price),
+					Err(e) => log::warn!(
+						"Failed to fetch price from {:?}: {:?}",
+						sp_std::str::from_utf8(&source.url),
+						e
+					),
+				}
+			}
+			let price = median(&mut prices).ok_or("No price endpoint answered")?;
+
+			Self::send_signed_tx(price)
+		}
+
+		fn fetch_price(source: &DataSource) -> Result<u32, http::Error> {
+			let url = sp_std::str::from_utf8(&source.url).map_err(|_| http::Error::Unknown)?;
+
+			// prepare for send request
+			let deadline = sp_io::offchain::timestamp()
+				.add(Duration::from_millis(source.deadline_ms.min(source::MAX_DEADLINE_MS)));
+			let mut request = http::Request::get(url);
+			for (name, value) in source.headers.iter() {
+				match (sp_std::str::from_utf8(name), sp_std::str::from_utf8(value)) {
+					(Ok(name), Ok(value)) => request = request.add_header(name, value),
+					_ => log::warn!("Skipping non UTF-8 header of {}", url),
+				}
+			}
+			let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+			if response.code != 200 {
+				log::warn!("Unexpected status code: {}", response.code);
+				return Err(http::Error::Unknown)
+			}
+			let body = response.body().collect::<Vec<u8>>();
+
+			// parse the response
+			source::extract_price(&body, &source.price_path).ok_or_else(|| {
+				log::warn!("No price in response: {:?}", sp_std::str::from_utf8(&body));
+				http::Error::Unknown
 			})
 		}
 
-		fn fetch_github_info() -> Result<GithubInfo, http::Error> {
-            // prepare for send request
-            let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(8_000));
-            let request =
-                http::Request::get("https://api.github.com/orgs/substrate-developer-hub");
-            let pending = request
-                .add_header("User-Agent", "Substrate-Offchain-Worker")
-                .deadline(deadline).send().map_err(|_| http::Error::IoError)?;
-            let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
-            if response.code != 200 {
-                log::warn!("Unexpected status code: {}", response.code);
-                return Err(http::Error::Unknown)
-            }
-            let body = response.body().collect::<Vec<u8>>();
-            let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-                log::warn!("No UTF8 body");
-                http::Error::Unknown
-            })?;
-
-            // parse the response str
-            let gh_info: GithubInfo =
-                serde_json::from_str(body_str).map_err(|_| http::Error::Unknown)?;
-
-            Ok(gh_info)
-        }
-
-		fn send_signed_tx(payload: Vec<u8>) -> Result<(), &'static str> {
-            let signer = Signer::<T, T::AuthorityId>::all_accounts();
-            if !signer.can_sign() {
-                return Err(
-                    "No local accounts available. Consider adding one via `author_insertKey` RPC.",
-                    )
-            }
-
-            let results = signer.send_signed_transaction(|_account| {
-
-                Call::submit_data { payload: payload.clone() }
-            });
-
-            for (acc, res) in &results {
-                match res {
-                    Ok(()) => log::info!("[{:?}] Submitted data:{:?}", acc.id, payload),
-                    Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
-                }
-            }
-
-            Ok(())
-        }
+		/// Sign `n` with each local `ocwd` key and submit it in an unsigned transaction.
+		pub(crate) fn send_unsigned_data(
+			block_number: T::BlockNumber,
+			n: u64,
+		) -> Result<(), &'static str> {
+			let results = Signer::<T, T::AuthorityId>::all_accounts().send_unsigned_transaction(
+				|account| DataPayload { block_number, n, public: account.public.clone() },
+				|payload, signature| Call::submit_data_unsigned_with_signed_payload {
+					payload,
+					signature,
+				},
+			);
+			if results.is_empty() {
+				return Err("No local accounts available to sign the payload.")
+			}
+
+			for (acc, res) in &results {
+				if res.is_err() {
+					log::error!("[{:?}] Failed in offchain_unsigned_tx", acc.id);
+				}
+			}
+
+			Ok(())
+		}
+
+		fn send_signed_tx(price: u32) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::all_accounts();
+			if !signer.can_sign() {
+				return Err(
+					"No local accounts available. Consider adding one via `author_insertKey` RPC.",
+				);
+			}
+
+			let results = signer.send_signed_transaction(|_account| Call::submit_price { price });
 
+			for (acc, res) in &results {
+				match res {
+					Ok(()) => log::info!("[{:?}] Submitted price: {}", acc.id, price),
+					Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
+				}
+			}
 
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
-    impl<T: Config> ValidateUnsigned for Pallet<T> {
-        type Call = Call<T>;
-
-        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            if let Call::submit_data_unsigned { n: _ } = call {
-                //let provide = b"submit_xxx_unsigned".to_vec();
-                ValidTransaction::with_tag_prefix("ExampleOffchainWorker")
-                    .priority(10000)
-                    .and_provides(1)
-                    .longevity(3)
-                    .propagate(true)
-                    .build()
-            } else {
-                InvalidTransaction::Call.into()
-            }
-        }
-    }
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_data_unsigned_with_signed_payload { payload, signature } = call {
+				if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+					return InvalidTransaction::BadProof.into()
+				}
+
+				let authority = payload.public.clone().into_account();
+				if !Authorities::<T>::get().contains(&authority) {
+					return InvalidTransaction::BadSigner.into()
+				}
+				let now = frame_system::Pallet::<T>::block_number();
+				if payload.block_number > now {
+					return InvalidTransaction::Future.into()
+				}
+				if payload.block_number.saturating_add(UNSIGNED_TX_LONGEVITY.into()) < now {
+					return InvalidTransaction::Stale.into()
+				}
+
+				// One payload per authority and block.
+				ValidTransaction::with_tag_prefix("ExampleOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((authority, payload.block_number))
+					.longevity(UNSIGNED_TX_LONGEVITY.into())
+					.propagate(true)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+	}
 }
@@ -1,11 +1,18 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_ocw;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, GenesisBuild};
 use frame_system as system;
-use sp_core::H256;
+use sp_core::{
+	offchain::{testing, OffchainWorkerExt, TransactionPoolExt},
+	sr25519::Signature,
+	H256,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+	RuntimeAppPublic,
 };
+use std::sync::Arc;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,10 +25,13 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		OcwModule: pallet_ocw::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned},
 	}
 );
 
+pub type Extrinsic = TestXt<Call, ()>;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
 impl system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
@@ -33,7 +43,7 @@ impl system::Config for Test {
 	type BlockNumber = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
 	type Event = Event;
@@ -49,11 +59,99 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+pub const PRICE_ENDPOINT_1: &str = "http://localhost:8001/price";
+pub const PRICE_ENDPOINT_2: &str = "http://localhost:8002/price";
+
+/// A source for `url` that reads the price at `path` and sends a `User-Agent` header.
+pub fn data_source(url: &str, path: &str) -> pallet_ocw::DataSource {
+	pallet_ocw::DataSource::new(url, path)
+		.and_then(|source| source.with_header("User-Agent", "Substrate-Offchain-Worker"))
+		.unwrap()
+}
+
+impl pallet_ocw::Config for Test {
 	type Event = Event;
+	type AuthorityId = pallet_ocw::crypto::OcwAuthId;
+	type MaxDataSources = ConstU32<2>;
+	type MaxPricesPerAccount = ConstU32<3>;
+	type MaxPriceAge = ConstU64<10>;
+	type MaxAuthorities = ConstU32<4>;
+	type UnsignedPriority = ConstU64<100>;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+}
+
+pub fn account(seed: u8) -> AccountId {
+	sp_core::sr25519::Public::from_raw([seed; 32])
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	pallet_ocw::GenesisConfig::<Test> {
+		authorities: vec![account(1), account(2), account(3)],
+		data_sources: vec![
+			data_source(PRICE_ENDPOINT_1, "USD"),
+			data_source(PRICE_ENDPOINT_2, "data.amount"),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A test externalities with offchain, transaction pool and keystore extensions, and one `ocwd`
+/// key whose public part is returned.
+pub fn new_offchain_test_ext() -> (
+	sp_io::TestExternalities,
+	Arc<parking_lot::RwLock<testing::OffchainState>>,
+	Arc<parking_lot::RwLock<testing::PoolState>>,
+	AccountId,
+) {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let public = SyncCryptoStore::sr25519_generate_new(
+		&keystore,
+		pallet_ocw::crypto::Public::ID,
+		Some("//Alice"),
+	)
+	.unwrap();
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	(ext, offchain_state, pool_state, public)
 }
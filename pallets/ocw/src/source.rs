@@ -0,0 +1,86 @@
+//! HTTP endpoints the offchain worker reads prices from.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+
+pub const MAX_URL_LEN: u32 = 256;
+pub const MAX_HEADERS: u32 = 8;
+pub const MAX_HEADER_LEN: u32 = 128;
+pub const MAX_PATH_LEN: u32 = 64;
+
+/// Deadline of sources built with `DataSource::new`.
+pub const DEFAULT_DEADLINE_MS: u64 = 8_000;
+/// Longest deadline `set_data_sources` accepts. The worker cuts longer deadlines of sources in
+/// local storage to it.
+pub const MAX_DEADLINE_MS: u64 = 10_000;
+
+/// A request header as `(name, value)`.
+pub type Header =
+	(BoundedVec<u8, ConstU32<MAX_HEADER_LEN>>, BoundedVec<u8, ConstU32<MAX_HEADER_LEN>>);
+
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DataSource {
+	/// The URL to `GET`.
+	pub url: BoundedVec<u8, ConstU32<MAX_URL_LEN>>,
+	pub headers: BoundedVec<Header, ConstU32<MAX_HEADERS>>,
+	/// How long to wait for the response, in milliseconds.
+	pub deadline_ms: u64,
+	/// Dot separated path to the price in the JSON response, e.g. `USD`, `data.amount` or
+	/// `prices.0`. The price may be a number or a numeric string.
+	pub price_path: BoundedVec<u8, ConstU32<MAX_PATH_LEN>>,
+}
+
+impl DataSource {
+	/// A source without extra headers and with `DEFAULT_DEADLINE_MS`, or `None` if `url` or
+	/// `price_path` is too long.
+	pub fn new(url: &str, price_path: &str) -> Option<Self> {
+		Some(DataSource {
+			url: bounded(url)?,
+			headers: Default::default(),
+			deadline_ms: DEFAULT_DEADLINE_MS,
+			price_path: bounded(price_path)?,
+		})
+	}
+
+	/// Add a request header, or return `None` if it does not fit.
+	pub fn with_header(mut self, name: &str, value: &str) -> Option<Self> {
+		self.headers.try_push((bounded(name)?, bounded(value)?)).ok()?;
+		Some(self)
+	}
+
+	pub fn with_deadline(mut self, deadline_ms: u64) -> Self {
+		self.deadline_ms = deadline_ms;
+		self
+	}
+}
+
+fn bounded<const N: u32>(value: &str) -> Option<BoundedVec<u8, ConstU32<N>>> {
+	BoundedVec::try_from(value.as_bytes().to_vec()).ok()
+}
+
+/// Follow `path` through the JSON `body` and read the price found there in cents.
+pub fn extract_price(body: &[u8], path: &[u8]) -> Option<u32> {
+	use serde_json::Value;
+
+	let root: Value = serde_json::from_slice(body).ok()?;
+	let mut value = &root;
+	for segment in sp_std::str::from_utf8(path).ok()?.split('.').filter(|s| !s.is_empty()) {
+		value = match value {
+			Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+			_ => value.get(segment)?,
+		};
+	}
+
+	let price = match value {
+		Value::Number(number) => number.as_f64()?,
+		Value::String(number) => number.parse::<f64>().ok()?,
+		_ => return None,
+	};
+	if !(0.0..(u32::MAX / 100) as f64).contains(&price) {
+		return None
+	}
+
+	Some((price * 100.0 + 0.5) as u32)
+}
@@ -1,13 +1,34 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	indexing_key,
+	mock::*,
+	source::{extract_price, MAX_DEADLINE_MS},
+	Authorities, DataPayload, DataSources, Error, IndexingData, Price, Submissions,
+	LAST_PROCESSED_KEY, LOCAL_DATA_SOURCES_KEY,
+};
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, unsigned::ValidateUnsigned};
+use frame_system::offchain::SignedPayload;
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{OffchainState, PendingRequest, PoolState},
+		Timestamp,
+	},
+	H256,
+};
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{BlakeTwo256, Hash},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
 
 #[test]
 fn it_works_for_default_value() {
 	new_test_ext().execute_with(|| {
 		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		assert_ok!(OcwModule::do_something(Origin::signed(account(1)), 42));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(OcwModule::something(), Some(42));
 	});
 }
 
@@ -15,6 +36,506 @@ fn it_works_for_default_value() {
 fn correct_error_for_none_value() {
 	new_test_ext().execute_with(|| {
 		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_noop!(OcwModule::cause_error(Origin::signed(account(1))), Error::<Test>::NoneValue);
+	});
+}
+
+#[test]
+fn median_works() {
+	assert_eq!(crate::median(&mut []), None);
+	assert_eq!(crate::median(&mut [7]), Some(7));
+	assert_eq!(crate::median(&mut [9, 1, 5]), Some(5));
+	assert_eq!(crate::median(&mut [9, 1, 5, 4]), Some(4));
+}
+
+#[test]
+fn extract_price_works() {
+	assert_eq!(extract_price(br#"{"USD": 15.5}"#, b"USD"), Some(1550));
+	assert_eq!(extract_price(br#"{"USD": 7}"#, b"USD"), Some(700));
+	assert_eq!(extract_price(br#"{"EUR": 15.5}"#, b"USD"), None);
+	assert_eq!(extract_price(br#"{"USD": -1}"#, b"USD"), None);
+	assert_eq!(extract_price(b"not json", b"USD"), None);
+
+	assert_eq!(extract_price(br#"{"data": {"amount": "16.50"}}"#, b"data.amount"), Some(1650));
+	assert_eq!(extract_price(br#"{"prices": [[1, 2.25]]}"#, b"prices.0.1"), Some(225));
+	assert_eq!(extract_price(br#"{"prices": [1]}"#, b"prices.1"), None);
+	assert_eq!(extract_price(br#"{"data": {"amount": "n/a"}}"#, b"data.amount"), None);
+	assert_eq!(extract_price(b"12", b""), Some(1200));
+}
+
+#[test]
+fn submit_price_keeps_a_ring_and_publishes_the_median() {
+	new_test_ext().execute_with(|| {
+		for price in [100, 200, 300, 400] {
+			assert_ok!(OcwModule::submit_price(Origin::signed(account(1)), price));
+		}
+		let prices = Submissions::<Test>::get(account(1))
+			.into_iter()
+			.map(|(price, _)| price)
+			.collect::<Vec<_>>();
+		assert_eq!(prices, vec![200, 300, 400]);
+		assert_eq!(Price::<Test>::get(), Some(300));
+
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(2)), 1_000));
+		assert_eq!(Price::<Test>::get(), Some(350));
+		System::assert_last_event(Event::OcwModule(crate::Event::PriceUpdated(350)));
+	});
+}
+
+#[test]
+fn every_authority_may_submit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OcwModule::add_authority(Origin::root(), account(4)));
+		for (seed, price) in [(1, 100), (2, 200), (3, 300), (4, 400)] {
+			assert_ok!(OcwModule::submit_price(Origin::signed(account(seed)), price));
+		}
+		assert_eq!(Price::<Test>::get(), Some(250));
+
+		assert_noop!(
+			OcwModule::submit_price(Origin::signed(account(5)), 100),
+			Error::<Test>::NotAuthority
+		);
+	});
+}
+
+#[test]
+fn stale_prices_leave_the_median() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(1)), 100));
+		System::set_block_number(5);
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(2)), 300));
+		assert_eq!(Price::<Test>::get(), Some(200));
+
+		// The price of account 1 is 11 blocks old, so its submissions are dropped.
+		System::set_block_number(12);
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(3)), 500));
+		assert!(!Submissions::<Test>::contains_key(account(1)));
+		assert_eq!(Price::<Test>::get(), Some(400));
+
+		// Stale prices are dropped from a ring that still gets new ones.
+		System::set_block_number(16);
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(2)), 700));
+		assert_eq!(Submissions::<Test>::get(account(2)).into_inner(), vec![(700, 16)]);
+		assert_eq!(Price::<Test>::get(), Some(600));
+	});
+}
+
+/// Run `fetch_and_submit_price` while holding the worker lock.
+fn fetch_and_submit_price() -> Result<(), &'static str> {
+	let mut lock = OcwModule::worker_lock();
+	let mut guard = lock.try_lock().unwrap();
+	OcwModule::fetch_and_submit_price(&mut guard)
+}
+
+fn expect_price_request(state: &mut OffchainState, uri: &str, response: &[u8]) {
+	state.expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		headers: vec![("User-Agent".into(), "Substrate-Offchain-Worker".into())],
+		response: Some(response.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+#[test]
+fn worker_submits_median_of_endpoints() {
+	let (mut ext, offchain_state, pool_state, public) = new_offchain_test_ext();
+	expect_price_request(&mut offchain_state.write(), PRICE_ENDPOINT_1, br#"{"USD": 15.5}"#);
+	expect_price_request(
+		&mut offchain_state.write(),
+		PRICE_ENDPOINT_2,
+		br#"{"data": {"amount": "16.5"}}"#,
+	);
+
+	ext.execute_with(|| {
+		assert_ok!(fetch_and_submit_price());
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		assert_eq!(tx.call, Call::OcwModule(crate::Call::submit_price { price: 1600 }));
+
+		assert_noop!(
+			OcwModule::submit_price(Origin::signed(public), 1600),
+			Error::<Test>::NotAuthority
+		);
+		assert_ok!(OcwModule::add_authority(Origin::root(), public));
+		assert_ok!(OcwModule::submit_price(Origin::signed(public), 1600));
+		assert_eq!(Price::<Test>::get(), Some(1600));
+	});
+}
+
+#[test]
+fn worker_skips_failing_endpoints() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_test_ext();
+	expect_price_request(&mut offchain_state.write(), PRICE_ENDPOINT_1, b"not json");
+	expect_price_request(
+		&mut offchain_state.write(),
+		PRICE_ENDPOINT_2,
+		br#"{"data": {"amount": "16.5"}}"#,
+	);
+
+	ext.execute_with(|| {
+		assert_ok!(fetch_and_submit_price());
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.call, Call::OcwModule(crate::Call::submit_price { price: 1650 }));
+	});
+}
+
+#[test]
+fn admin_sets_data_sources() {
+	new_test_ext().execute_with(|| {
+		let sources = vec![data_source("http://localhost:8003/price", "price")];
+
+		assert_noop!(
+			OcwModule::set_data_sources(Origin::signed(account(1)), sources.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			OcwModule::set_data_sources(Origin::root(), vec![sources[0].clone(); 3]),
+			Error::<Test>::TooManyDataSources
+		);
+		assert_noop!(
+			OcwModule::set_data_sources(
+				Origin::root(),
+				vec![sources[0].clone().with_deadline(MAX_DEADLINE_MS + 1)]
+			),
+			Error::<Test>::DeadlineTooLong
+		);
+
+		assert_ok!(OcwModule::set_data_sources(Origin::root(), sources.clone()));
+		assert_eq!(DataSources::<Test>::get().into_inner(), sources);
+		System::assert_last_event(Event::OcwModule(crate::Event::DataSourcesUpdated(1)));
+	});
+}
+
+#[test]
+fn worker_uses_on_chain_data_sources() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_test_ext();
+	let source = data_source("http://localhost:8003/price", "price").with_deadline(1_000);
+	offchain_state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:8003/price".into(),
+		headers: vec![("User-Agent".into(), "Substrate-Offchain-Worker".into())],
+		response: Some(br#"{"price": 3}"#.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+
+	ext.execute_with(|| {
+		assert_ok!(OcwModule::set_data_sources(Origin::root(), vec![source]));
+		assert_ok!(fetch_and_submit_price());
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.call, Call::OcwModule(crate::Call::submit_price { price: 300 }));
+	});
+}
+
+#[test]
+fn local_data_sources_override_on_chain_ones() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_test_ext();
+	expect_price_request(
+		&mut offchain_state.write(),
+		"http://localhost:9000/price",
+		br#"{"USD": 2}"#,
+	);
+
+	ext.execute_with(|| {
+		let local = vec![data_source("http://localhost:9000/price", "USD")];
+		StorageValueRef::persistent(LOCAL_DATA_SOURCES_KEY).set(&local);
+		assert_eq!(OcwModule::active_data_sources(), local);
+
+		assert_ok!(fetch_and_submit_price());
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.call, Call::OcwModule(crate::Call::submit_price { price: 200 }));
+	});
+}
+
+/// Run the worker's unsigned submission for block 1 and return the call it put in the pool.
+fn unsigned_data_call(pool_state: &RwLock<PoolState>) -> Call {
+	assert_ok!(OcwModule::send_unsigned_data(1, 42));
+
+	let tx = pool_state.write().transactions.pop().unwrap();
+	let tx = Extrinsic::decode(&mut &*tx).unwrap();
+	assert_eq!(tx.signature, None);
+	tx.call
+}
+
+#[test]
+fn worker_submits_signed_payload() {
+	let (mut ext, _, pool_state, public) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		Authorities::<Test>::put(vec![public].try_into().unwrap());
+
+		let (payload, signature) = match unsigned_data_call(&pool_state) {
+			Call::OcwModule(crate::Call::submit_data_unsigned_with_signed_payload {
+				payload,
+				signature,
+			}) => (payload, signature),
+			call => panic!("unexpected call: {:?}", call),
+		};
+		assert_eq!(payload, DataPayload { block_number: 1, n: 42, public });
+		assert!(SignedPayload::<Test>::verify::<crate::crypto::OcwAuthId>(
+			&payload,
+			signature.clone()
+		));
+
+		let call = crate::Call::submit_data_unsigned_with_signed_payload { payload, signature };
+		let valid = OcwModule::validate_unsigned(TransactionSource::Local, &call).unwrap();
+		assert_eq!(valid.priority, 100);
+		assert_eq!(valid.provides, vec![("ExampleOffchainWorker", (public, 1u64)).encode()]);
+	});
+}
+
+#[test]
+fn signed_payload_from_unknown_authority_is_rejected() {
+	let (mut ext, _, pool_state, _) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		let call = match unsigned_data_call(&pool_state) {
+			Call::OcwModule(call) => call,
+			call => panic!("unexpected call: {:?}", call),
+		};
+		assert_eq!(
+			OcwModule::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::BadSigner.into()
+		);
+	});
+}
+
+#[test]
+fn tampered_future_or_stale_payload_is_rejected() {
+	let (mut ext, _, pool_state, public) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		Authorities::<Test>::put(vec![public].try_into().unwrap());
+
+		let (payload, signature) = match unsigned_data_call(&pool_state) {
+			Call::OcwModule(crate::Call::submit_data_unsigned_with_signed_payload {
+				payload,
+				signature,
+			}) => (payload, signature),
+			call => panic!("unexpected call: {:?}", call),
+		};
+
+		let tampered = crate::Call::submit_data_unsigned_with_signed_payload {
+			payload: DataPayload { n: 43, ..payload.clone() },
+			signature: signature.clone(),
+		};
+		assert_eq!(
+			OcwModule::validate_unsigned(TransactionSource::External, &tampered),
+			InvalidTransaction::BadProof.into()
+		);
+
+		let call = crate::Call::submit_data_unsigned_with_signed_payload { payload, signature };
+		System::set_block_number(0);
+		assert_eq!(
+			OcwModule::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Future.into()
+		);
+
+		System::set_block_number(4);
+		assert_ok!(OcwModule::validate_unsigned(TransactionSource::External, &call));
+		System::set_block_number(5);
+		assert_eq!(
+			OcwModule::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+#[test]
+fn submitted_data_is_indexed_per_parent_and_extrinsic() {
+	let mut ext = new_test_ext();
+	let parent = H256::repeat_byte(1);
+
+	// Submit `n` as the extrinsic at `extrinsic_index` and return the hash of that extrinsic.
+	let submit = |extrinsic_index: u32, n: u64| {
+		frame_support::storage::unhashed::put(
+			sp_core::storage::well_known_keys::EXTRINSIC_INDEX,
+			&extrinsic_index,
+		);
+		let extrinsic = (b"extrinsic", n).encode();
+		System::note_extrinsic(extrinsic.clone());
+
+		let payload = DataPayload { block_number: 1, n, public: account(1) };
+		let signature = sp_core::sr25519::Signature::from_raw([0; 64]);
+		assert_ok!(OcwModule::submit_data_unsigned_with_signed_payload(
+			Origin::none(),
+			payload,
+			signature
+		));
+		BlakeTwo256::hash(&extrinsic)
+	};
+
+	let (first, second, sibling) = ext.execute_with(|| {
+		System::initialize(&2, &parent, &Default::default());
+		let first = submit(0, 20);
+		let second = submit(1, 21);
+
+		// A competing block on the same parent with another extrinsic at index 0.
+		System::initialize(&2, &parent, &Default::default());
+		(first, second, submit(0, 22))
+	});
+	ext.persist_offchain_overlay();
+
+	let indexed = |parent_hash: H256, extrinsic_hash: H256| {
+		ext.offchain_db()
+			.get(&indexing_key(parent_hash, extrinsic_hash))
+			.map(|data| IndexingData::decode(&mut &*data).unwrap().1)
+	};
+	assert_eq!(indexed(parent, first), Some(20));
+	assert_eq!(indexed(parent, second), Some(21));
+	assert_eq!(indexed(parent, sibling), Some(22));
+	assert_eq!(indexed(H256::repeat_byte(2), first), None);
+}
+
+#[test]
+fn admin_manages_authorities() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(OcwModule::authorities().into_inner(), vec![account(1), account(2), account(3)]);
+
+		assert_noop!(
+			OcwModule::add_authority(Origin::signed(account(1)), account(4)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			OcwModule::add_authority(Origin::root(), account(1)),
+			Error::<Test>::AlreadyAuthority
+		);
+		assert_ok!(OcwModule::add_authority(Origin::root(), account(4)));
+		System::assert_last_event(Event::OcwModule(crate::Event::AuthorityAdded(account(4))));
+		assert_noop!(
+			OcwModule::add_authority(Origin::root(), account(5)),
+			Error::<Test>::TooManyAuthorities
+		);
+
+		assert_noop!(
+			OcwModule::remove_authority(Origin::root(), account(5)),
+			Error::<Test>::NotAuthority
+		);
+		assert_ok!(OcwModule::remove_authority(Origin::root(), account(4)));
+		System::assert_last_event(Event::OcwModule(crate::Event::AuthorityRemoved(account(4))));
+		assert!(!OcwModule::authorities().contains(&account(4)));
+	});
+}
+
+#[test]
+fn removed_authority_prices_leave_the_median() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(1)), 100));
+		assert_ok!(OcwModule::submit_price(Origin::signed(account(2)), 300));
+		assert_eq!(Price::<Test>::get(), Some(200));
+
+		assert_ok!(OcwModule::remove_authority(Origin::root(), account(2)));
+		assert!(!Submissions::<Test>::contains_key(account(2)));
+		assert_eq!(Price::<Test>::get(), Some(100));
+		assert_noop!(
+			OcwModule::submit_price(Origin::signed(account(2)), 300),
+			Error::<Test>::NotAuthority
+		);
+
+		assert_ok!(OcwModule::remove_authority(Origin::root(), account(1)));
+		assert_eq!(Price::<Test>::get(), None);
+		System::assert_last_event(Event::OcwModule(crate::Event::PriceCleared));
+	});
+}
+
+/// Run the worker hook for `block_number` without any data sources and return how many
+/// transactions it added to the pool.
+fn run_worker(pool_state: &RwLock<PoolState>, block_number: u64) -> usize {
+	let before = pool_state.read().transactions.len();
+	OcwModule::offchain_worker(block_number);
+	pool_state.read().transactions.len() - before
+}
+
+#[test]
+fn worker_runs_once_per_block() {
+	let (mut ext, _, pool_state, _) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		assert_ok!(OcwModule::set_data_sources(Origin::root(), vec![]));
+
+		assert_eq!(run_worker(&pool_state, 1), 1);
+		assert_eq!(StorageValueRef::persistent(LAST_PROCESSED_KEY).get::<u64>(), Ok(Some(1)));
+		assert_eq!(run_worker(&pool_state, 1), 0);
+
+		System::set_block_number(3);
+		assert_eq!(run_worker(&pool_state, 3), 1);
+		assert_eq!(run_worker(&pool_state, 2), 0);
+	});
+}
+
+#[test]
+fn overlapping_worker_is_skipped() {
+	let (mut ext, _, pool_state, _) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		assert_ok!(OcwModule::set_data_sources(Origin::root(), vec![]));
+
+		// A first worker for block 1 is still running when the second one starts.
+		let mut lock = OcwModule::worker_lock();
+		let guard = lock.try_lock().unwrap();
+		assert_eq!(run_worker(&pool_state, 1), 0);
+		assert_eq!(StorageValueRef::persistent(LAST_PROCESSED_KEY).get::<u64>(), Ok(None));
+
+		drop(guard);
+		assert_eq!(run_worker(&pool_state, 1), 1);
+	});
+}
+
+#[test]
+fn stale_worker_lock_expires() {
+	let (mut ext, offchain_state, pool_state, _) = new_offchain_test_ext();
+
+	ext.execute_with(|| {
+		assert_ok!(OcwModule::set_data_sources(Origin::root(), vec![]));
+
+		// A worker died without releasing its lock.
+		let mut lock = OcwModule::worker_lock();
+		core::mem::forget(lock.try_lock().unwrap());
+		assert_eq!(run_worker(&pool_state, 1), 0);
+
+		System::set_block_number(5);
+		assert_eq!(run_worker(&pool_state, 5), 0);
+
+		offchain_state.write().timestamp = Timestamp::from_unix_millis(20_001);
+		assert_eq!(run_worker(&pool_state, 5), 1);
+	});
+}
+
+#[test]
+fn worker_lock_is_extended_between_sources() {
+	let (mut ext, offchain_state, _, _) = new_offchain_test_ext();
+	expect_price_request(&mut offchain_state.write(), PRICE_ENDPOINT_1, br#"{"USD": 15.5}"#);
+	expect_price_request(
+		&mut offchain_state.write(),
+		PRICE_ENDPOINT_2,
+		br#"{"data": {"amount": "16.5"}}"#,
+	);
+
+	ext.execute_with(|| {
+		let mut lock = OcwModule::worker_lock();
+		let mut guard = lock.try_lock().unwrap();
+
+		// The sources are queried late, and the lock is extended from there.
+		offchain_state.write().timestamp = Timestamp::from_unix_millis(15_000);
+		System::set_block_number(5);
+		assert_ok!(OcwModule::fetch_and_submit_price(&mut guard));
+		core::mem::forget(guard);
+
+		// Past the block deadline, but not the extended time deadline.
+		System::set_block_number(10);
+		offchain_state.write().timestamp = Timestamp::from_unix_millis(25_000);
+		let mut lock = OcwModule::worker_lock();
+		assert!(lock.try_lock().is_err());
 	});
 }
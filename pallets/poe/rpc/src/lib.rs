@@ -0,0 +1,247 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_poe::{ClaimId, CustodyRecord, HashAlgorithm};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+};
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+
+/// Most claims returned by one `poe_claimsOf` call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// A document to verify: its raw bytes, hashed by the node, or a digest computed off chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Document {
+	Raw(Bytes),
+	Digest(Bytes),
+}
+
+/// Who holds a verified claim and the block it was created in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Verification<AccountId, BlockNumber, BlockHash> {
+	pub owner: AccountId,
+	pub block_number: BlockNumber,
+	pub block_hash: BlockHash,
+}
+
+#[rpc(client, server)]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber> {
+	/// The recorded chain of custody of the claim on `digest`, oldest first.
+	#[method(name = "poe_claimHistory")]
+	fn claim_history(
+		&self,
+		algorithm: HashAlgorithm,
+		digest: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<CustodyRecord<AccountId, BlockNumber>>>;
+
+	/// The current holder of the claim on `document` hashed with `algorithm` and the block the
+	/// claim was created in, if there is one.
+	#[method(name = "poe_verify")]
+	fn verify(
+		&self,
+		algorithm: HashAlgorithm,
+		document: Document,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Verification<AccountId, BlockNumber, BlockHash>>>;
+
+	/// Up to `limit` claims of `owner`, at most `MAX_PAGE_SIZE`. Pass the last claim of a page
+	/// as `start_after` to get the next one.
+	#[method(name = "poe_claimsOf")]
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<ClaimId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ClaimId>>;
+
+	/// The block the Merkle `root` was anchored in, if `proof` shows that `leaf` is its leaf at
+	/// `leaf_index`.
+	#[method(name = "poe_verifyMerkleInclusion")]
+	fn verify_merkle_inclusion(
+		&self,
+		algorithm: HashAlgorithm,
+		root: Bytes,
+		leaf: Bytes,
+		leaf_index: u32,
+		proof: Vec<Bytes>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BlockNumber>>;
+}
+
+/// Provides RPC methods to query proofs of existence.
+pub struct Poe<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Poe<C, Block> {
+	/// Creates a new instance of the PoE RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The digest length does not match the hash algorithm.
+	InvalidDigest,
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The node does not know the block a claim was created in among the ancestors of `at`.
+	BlockNotFound,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::InvalidDigest => 1,
+			Error::RuntimeError => 2,
+			Error::BlockNotFound => 3,
+		}
+	}
+}
+
+fn claim_id(algorithm: HashAlgorithm, digest: &[u8]) -> RpcResult<ClaimId> {
+	ClaimId::new(algorithm, digest).ok_or_else(|| {
+		CallError::Custom(ErrorObject::owned(
+			Error::InvalidDigest.into(),
+			"Digest length does not match the hash algorithm.",
+			None::<()>,
+		))
+		.into()
+	})
+}
+
+/// The hash of the block numbered `number` in the chain ending in `at`. Walks back from `at` only
+/// until it reaches the canonical chain, whose hashes the client looks up by number.
+fn ancestor_hash<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	at: Block::Hash,
+	number: NumberFor<Block>,
+) -> RpcResult<Option<Block::Hash>> {
+	let mut hash = at;
+	loop {
+		let header = match client.header(BlockId::Hash(hash)).map_err(runtime_error)? {
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		if *header.number() <= number {
+			return Ok((*header.number() == number).then_some(hash))
+		}
+		if client.hash(*header.number()).map_err(runtime_error)? == Some(hash) {
+			return client.hash(number).map_err(runtime_error)
+		}
+		hash = *header.parent_hash();
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query proofs of existence.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, BlockNumber> PoeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for Poe<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Copy + Into<NumberFor<Block>> + Serialize + Send + Sync + 'static,
+{
+	fn claim_history(
+		&self,
+		algorithm: HashAlgorithm,
+		digest: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<CustodyRecord<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_history(&at, claim_id(algorithm, &digest)?).map_err(runtime_error)
+	}
+
+	fn verify(
+		&self,
+		algorithm: HashAlgorithm,
+		document: Document,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Verification<AccountId, BlockNumber, <Block as BlockT>::Hash>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let at = BlockId::hash(at_hash);
+
+		let claim = match document {
+			Document::Raw(data) => ClaimId::from_payload(algorithm, &data),
+			Document::Digest(digest) => claim_id(algorithm, &digest)?,
+		};
+		let (owner, block_number) = match api.claim(&at, claim).map_err(runtime_error)? {
+			Some(claim) => claim,
+			None => return Ok(None),
+		};
+		let block_hash =
+			ancestor_hash(&*self.client, at_hash, block_number.into())?.ok_or_else(|| {
+				CallError::Custom(ErrorObject::owned(
+					Error::BlockNotFound.into(),
+					"Unable to find the block the claim was created in.",
+					None::<()>,
+				))
+			})?;
+
+		Ok(Some(Verification { owner, block_number, block_hash }))
+	}
+
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<ClaimId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ClaimId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claims_of(&at, owner, start_after, limit.min(MAX_PAGE_SIZE))
+			.map_err(runtime_error)
+	}
+
+	fn verify_merkle_inclusion(
+		&self,
+		algorithm: HashAlgorithm,
+		root: Bytes,
+		leaf: Bytes,
+		leaf_index: u32,
+		proof: Vec<Bytes>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let proof = proof.into_iter().map(|sibling| sibling.0).collect();
+		api.verify_merkle_inclusion(&at, claim_id(algorithm, &root)?, leaf.0, leaf_index, proof)
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,37 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_poe::{ClaimId, CustodyRecord};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The owner of a claim and the block it was created in.
+		fn claim(claim: ClaimId) -> Option<(AccountId, BlockNumber)>;
+
+		/// Up to `limit` claims of `owner`, starting after `start_after`, the last claim of the
+		/// previous page.
+		fn claims_of(owner: AccountId, start_after: Option<ClaimId>, limit: u32) -> Vec<ClaimId>;
+
+		/// The recorded chain of custody of a claim, oldest first.
+		fn claim_history(claim: ClaimId) -> Vec<CustodyRecord<AccountId, BlockNumber>>;
+
+		/// Who held a claim at the given block, if known.
+		fn claim_holder_at(claim: ClaimId, at: BlockNumber) -> Option<AccountId>;
+
+		/// The block a Merkle root was anchored in, if `proof` shows that `leaf` is its leaf at
+		/// `leaf_index`.
+		fn verify_merkle_inclusion(
+			root: ClaimId,
+			leaf: Vec<u8>,
+			leaf_index: u32,
+			proof: Vec<Vec<u8>>,
+		) -> Option<BlockNumber>;
+	}
+}
@@ -0,0 +1,179 @@
+//! Benchmarking setup for pallet-poe
+
+use super::*;
+
+use crate::pallet::BalanceOf;
+#[allow(unused)]
+use crate::Pallet as PoeModule;
+use frame_benchmarking::{account, benchmarks, vec, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, Get, Hooks},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+const ALGORITHM: HashAlgorithm = HashAlgorithm::Blake2_256;
+
+/// A distinct digest for every `i`.
+fn digest(i: u32) -> Vec<u8> {
+	ALGORITHM.hash(&i.to_le_bytes()).into_inner()
+}
+
+fn claim_id(i: u32) -> ClaimId {
+	ClaimId::new(ALGORITHM, &digest(i)).unwrap()
+}
+
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	funded::<T>(whitelisted_caller())
+}
+
+fn create<T: Config>(owner: &T::AccountId, i: u32, expires_at: Option<T::BlockNumber>) {
+	PoeModule::<T>::create_claim(
+		RawOrigin::Signed(owner.clone()).into(),
+		ALGORITHM,
+		digest(i),
+		expires_at,
+	)
+	.unwrap();
+}
+
+/// Leave room for a single more claim expiring at `at`.
+fn fill_expiring<T: Config>(at: T::BlockNumber, offset: u32) {
+	let expiring = (0..T::MaxExpiringPerBlock::get() - 1)
+		.map(|i| claim_id(offset + i))
+		.collect::<Vec<_>>();
+	ExpiringClaims::<T>::insert(at, BoundedVec::try_from(expiring).unwrap());
+}
+
+/// Fill the custody history of `claim`, so the next transfer has to prune it.
+fn fill_history<T: Config>(claim: &ClaimId, holder: &T::AccountId) {
+	let record = CustodyRecord { holder: holder.clone(), block: Default::default(), memo: None };
+	let history = vec![record; T::MaxHistoryLength::get() as usize];
+	ClaimHistory::<T>::insert(claim, BoundedVec::try_from(history).unwrap());
+}
+
+fn in_blocks<T: Config>(n: u32) -> T::BlockNumber {
+	frame_system::Pallet::<T>::block_number() + n.into()
+}
+
+benchmarks! {
+	create_claim {
+		let caller = funded_caller::<T>();
+		let expires_at = in_blocks::<T>(10);
+		fill_expiring::<T>(expires_at, 1_000);
+	}: _(RawOrigin::Signed(caller.clone()), ALGORITHM, digest(0), Some(expires_at))
+	verify {
+		assert_eq!(Proofs::<T>::get(claim_id(0)).map(|(owner, _, _)| owner), Some(caller));
+	}
+
+	create_claim_from_payload {
+		let l in 0 .. T::MaxClaimLength::get();
+		let caller = funded_caller::<T>();
+		let payload = vec![1u8; l as usize];
+	}: _(RawOrigin::Signed(caller), ALGORITHM, payload.clone())
+	verify {
+		assert!(Proofs::<T>::contains_key(ClaimId::from_payload(ALGORITHM, &payload)));
+	}
+
+	renew_claim {
+		let caller = funded_caller::<T>();
+		let expires_at = in_blocks::<T>(10);
+		let renewed_at = in_blocks::<T>(20);
+		fill_expiring::<T>(expires_at, 1_000);
+		fill_expiring::<T>(renewed_at, 2_000);
+		create::<T>(&caller, 0, Some(expires_at));
+	}: _(RawOrigin::Signed(caller), ALGORITHM, digest(0), Some(renewed_at))
+	verify {
+		assert_eq!(ClaimExpiry::<T>::get(claim_id(0)), Some(renewed_at));
+	}
+
+	revoke_claim {
+		let caller = funded_caller::<T>();
+		let expires_at = in_blocks::<T>(10);
+		fill_expiring::<T>(expires_at, 1_000);
+		create::<T>(&caller, 0, Some(expires_at));
+		fill_history::<T>(&claim_id(0), &caller);
+		MerkleRoots::<T>::insert(claim_id(0), (8, expires_at));
+	}: _(RawOrigin::Signed(caller), ALGORITHM, digest(0))
+	verify {
+		assert!(!Proofs::<T>::contains_key(claim_id(0)));
+	}
+
+	transfer_claim {
+		let m in 0 .. crate::claim::MAX_MEMO_LEN;
+		let caller = funded_caller::<T>();
+		let dest = funded::<T>(account("dest", 0, 0));
+		create::<T>(&caller, 0, None);
+		fill_history::<T>(&claim_id(0), &caller);
+	}: _(RawOrigin::Signed(caller), dest.clone(), ALGORITHM, digest(0), Some(vec![0; m as usize]))
+	verify {
+		assert_eq!(Proofs::<T>::get(claim_id(0)).map(|(owner, _, _)| owner), Some(dest));
+	}
+
+	create_merkle_claim {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller), ALGORITHM, digest(0), 8)
+	verify {
+		assert!(MerkleRoots::<T>::contains_key(claim_id(0)));
+	}
+
+	create_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let claims = (0..n).map(|i| (ALGORITHM, digest(i))).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), claims, BatchMode::AllOrNothing)
+	verify {
+		assert!((0..n).all(|i| Proofs::<T>::contains_key(claim_id(i))));
+	}
+
+	revoke_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		for i in 0..n {
+			create::<T>(&caller, i, None);
+		}
+		let claims = (0..n).map(|i| (ALGORITHM, digest(i))).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), claims, BatchMode::AllOrNothing)
+	verify {
+		assert!((0..n).all(|i| !Proofs::<T>::contains_key(claim_id(i))));
+	}
+
+	transfer_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let dest = funded::<T>(account("dest", 0, 0));
+		for i in 0..n {
+			create::<T>(&caller, i, None);
+			fill_history::<T>(&claim_id(i), &caller);
+		}
+		let claims = (0..n).map(|i| (dest.clone(), ALGORITHM, digest(i))).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), claims, BatchMode::AllOrNothing)
+	verify {
+		for i in 0..n {
+			assert_eq!(Proofs::<T>::get(claim_id(i)).map(|(owner, _, _)| owner), Some(dest.clone()));
+		}
+	}
+
+	on_initialize {
+		let n in 0 .. T::MaxExpiringPerBlock::get();
+		let caller = funded_caller::<T>();
+		let expires_at = in_blocks::<T>(1);
+		for i in 0..n {
+			create::<T>(&caller, i, Some(expires_at));
+		}
+	}: {
+		PoeModule::<T>::on_initialize(expires_at);
+	}
+	verify {
+		assert!((0..n).all(|i| !Proofs::<T>::contains_key(claim_id(i))));
+	}
+
+	impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,74 @@
+//! Claim identifiers: a content digest tagged with the algorithm that produced it.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Longest digest any supported algorithm produces.
+pub const MAX_DIGEST_LEN: u32 = 64;
+
+/// Hash functions a claim digest may be computed with.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum HashAlgorithm {
+	Blake2_256,
+	Sha2_256,
+	Keccak256,
+}
+
+impl HashAlgorithm {
+	/// Length in bytes of the digests this algorithm produces.
+	pub fn digest_len(&self) -> usize {
+		match self {
+			HashAlgorithm::Blake2_256 | HashAlgorithm::Sha2_256 | HashAlgorithm::Keccak256 => 32,
+		}
+	}
+
+	pub fn hash(&self, data: &[u8]) -> BoundedVec<u8, ConstU32<MAX_DIGEST_LEN>> {
+		let digest = match self {
+			HashAlgorithm::Blake2_256 => sp_io::hashing::blake2_256(data),
+			HashAlgorithm::Sha2_256 => sp_io::hashing::sha2_256(data),
+			HashAlgorithm::Keccak256 => sp_io::hashing::keccak_256(data),
+		};
+		BoundedVec::try_from(digest.to_vec()).expect("every digest fits MAX_DIGEST_LEN; qed")
+	}
+}
+
+/// The key a proof is stored under.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClaimId {
+	pub algorithm: HashAlgorithm,
+	pub digest: BoundedVec<u8, ConstU32<MAX_DIGEST_LEN>>,
+}
+
+impl ClaimId {
+	/// Build a claim id from a digest computed off chain, checking its length against
+	/// `algorithm`.
+	pub fn new(algorithm: HashAlgorithm, digest: &[u8]) -> Option<Self> {
+		if digest.len() != algorithm.digest_len() {
+			return None
+		}
+		let digest = BoundedVec::try_from(digest.to_vec()).ok()?;
+		Some(ClaimId { algorithm, digest })
+	}
+
+	/// The claim id of `payload` hashed with `algorithm`.
+	pub fn from_payload(algorithm: HashAlgorithm, payload: &[u8]) -> Self {
+		ClaimId { algorithm, digest: algorithm.hash(payload) }
+	}
+}
+
+/// Longest memo a transfer may attach to the custody history.
+pub const MAX_MEMO_LEN: u32 = 256;
+
+/// One entry of a claim's chain of custody: `holder` held the claim from `block` on.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CustodyRecord<AccountId, BlockNumber> {
+	pub holder: AccountId,
+	pub block: BlockNumber,
+	pub memo: Option<BoundedVec<u8, ConstU32<MAX_MEMO_LEN>>>,
+}
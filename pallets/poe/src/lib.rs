@@ -9,39 +9,156 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod claim;
+pub mod merkle;
+pub mod migrations;
+pub mod weights;
+
+pub use claim::{ClaimId, CustodyRecord, HashAlgorithm};
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_storage_layer,
+		traits::{BalanceStatus, Currency, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
 	use sp_std::prelude::*;
 
+	use crate::{
+		claim::{ClaimId, CustodyRecord, HashAlgorithm, MAX_MEMO_LEN},
+		merkle, migrations, WeightInfo,
+	};
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// Longest payload `create_claim_from_payload` will hash on chain.
 		#[pallet::constant]
 		type MaxClaimLength: Get<u32>;
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Deposit reserved per byte of an encoded claim id, refunded when the claim is revoked.
+		#[pallet::constant]
+		type ClaimDeposit: Get<BalanceOf<Self>>;
+		/// Custody records kept per claim. Once full, the oldest transfer is pruned while the
+		/// creation record is always kept, so this must be at least 2.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+		/// Most items a single batch call may carry.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Most claims that may expire in the same block, bounding the work of `on_initialize`.
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+		/// Most claims a single account may hold, bounding the `ClaimsByOwner` index.
+		#[pallet::constant]
+		type MaxClaimsPerOwner: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// How a batch call treats an item that fails.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum BatchMode {
+		/// The first failure reverts the whole batch and is returned as the call's error.
+		AllOrNothing,
+		/// Failed items are reverted on their own and reported with `BatchItemFailed`.
+		BestEffort,
 	}
 
+	pub type CustodyRecordOf<T> = CustodyRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
 	#[pallet::pallet]
 	// #[pallet::without_storage_info]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// Owner, block of the last change and the deposit reserved for each claim. The deposit is
+	/// stored so that refunds stay correct if `ClaimDeposit` changes later.
 	#[pallet::storage]
-	pub type Proofs<T: Config> = StorageMap<
+	pub type Proofs<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimId, (T::AccountId, T::BlockNumber, BalanceOf<T>)>;
+
+	/// The claims held by each account, kept in step with the owners in `Proofs`.
+	#[pallet::storage]
+	pub type ClaimsByOwner<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		BoundedVec<u8, T::MaxClaimLength>,
-		(T::AccountId, T::BlockNumber),
+		T::AccountId,
+		Blake2_128Concat,
+		ClaimId,
+		(),
+		OptionQuery,
 	>;
 
+	/// Number of claims held by each account.
+	#[pallet::storage]
+	pub type ClaimsByOwnerCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Chain of custody of each claim, starting with its creator.
+	#[pallet::storage]
+	pub type ClaimHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ClaimId,
+		BoundedVec<CustodyRecordOf<T>, T::MaxHistoryLength>,
+		ValueQuery,
+	>;
+
+	/// Leaf count and anchoring block of claims created with `create_merkle_claim`.
+	#[pallet::storage]
+	pub type MerkleRoots<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimId, (u32, T::BlockNumber)>;
+
+	/// The block at which an expiring claim is removed.
+	#[pallet::storage]
+	pub type ClaimExpiry<T: Config> = StorageMap<_, Blake2_128Concat, ClaimId, T::BlockNumber>;
+
+	/// Claims to remove in `on_initialize`, keyed by their expiry block.
+	#[pallet::storage]
+	pub type ExpiringClaims<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<ClaimId, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// For claims whose history was pruned, the block of the first transfer that was dropped.
+	/// Who held the claim from then until the oldest transfer still recorded is no longer known.
+	#[pallet::storage]
+	pub type HistoryPrunedFrom<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimId, T::BlockNumber>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		ClaimCreated(T::AccountId, Vec<u8>),
-		ClaimRevoked(T::AccountId, Vec<u8>),
-		ClaimTransferred(T::AccountId, T::AccountId, Vec<u8>),
+		ClaimCreated(T::AccountId, ClaimId),
+		ClaimRevoked(T::AccountId, ClaimId),
+		ClaimTransferred(T::AccountId, T::AccountId, ClaimId),
+		/// An item of a best-effort batch failed and was skipped. [index, error]
+		BatchItemFailed(u32, DispatchError),
+		/// A claim reached its expiry block and was removed. [owner, claim]
+		ClaimExpired(T::AccountId, ClaimId),
+		/// The expiry of a claim was moved, or cleared with `None`. [owner, claim, expires_at]
+		ClaimRenewed(T::AccountId, ClaimId, Option<T::BlockNumber>),
 	}
 
 	#[pallet::error]
@@ -50,70 +167,442 @@ pub mod pallet {
 		ClaimTooLong,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// The digest length does not match the hash algorithm.
+		InvalidDigestLength,
+		/// The sender cannot reserve the claim deposit.
+		NotEnoughBalance,
+		/// The transfer memo is longer than `MAX_MEMO_LEN`.
+		MemoTooLong,
+		/// The batch carries more than `MaxBatchSize` items.
+		BatchTooLarge,
+		/// A Merkle root must cover at least one leaf.
+		InvalidLeafCount,
+		/// The expiry block must be in the future.
+		InvalidExpiry,
+		/// Too many claims already expire in the requested block.
+		TooManyExpiring,
+		/// The account already holds `MaxClaimsPerOwner` claims.
+		TooManyClaims,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+				.saturating_add(migrations::v2::migrate::<T>())
+				.saturating_add(migrations::v3::migrate::<T>())
+				.saturating_add(migrations::v4::migrate::<T>())
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = ExpiringClaims::<T>::take(now);
+			let count = expiring.len() as u32;
+
+			for claim in expiring {
+				// The bucket of `now` is already gone, so only the claim's own entry is left.
+				ClaimExpiry::<T>::remove(&claim);
+				if let Some((owner, _, deposit)) = Proofs::<T>::get(&claim) {
+					Self::remove_claim(&owner, &claim, deposit);
+					Self::deposit_event(Event::ClaimExpired(owner, claim));
+				}
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
+
+		fn integrity_test() {
+			// `record_custody` always keeps the creation record, so a shorter history could
+			// never record a transfer.
+			assert!(T::MaxHistoryLength::get() >= 2, "`MaxHistoryLength` must be at least 2");
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
-		pub fn create_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+		/// Claim a document by the digest of its content, computed off chain with `algorithm`.
+		/// With `expires_at` set, the claim is removed and its deposit refunded at that block.
+		#[pallet::weight(T::WeightInfo::create_claim())]
+		#[frame_support::transactional]
+		pub fn create_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone())
-				.map_err(|_| Error::<T>::ClaimTooLong)?;
+			let claim = Self::claim_id(algorithm, &digest)?;
+			Self::do_create_claim(sender, claim.clone())?;
+			if let Some(expires_at) = expires_at {
+				Self::schedule_expiry(&claim, expires_at)?;
+			}
 
-			ensure!(!Proofs::<T>::contains_key(&bounded_claim), Error::<T>::ProofAlreadyExist);
+			Ok(().into())
+		}
 
-			Proofs::<T>::insert(
-				&bounded_claim,
-				(sender.clone(), frame_system::Pallet::<T>::block_number()),
-			);
+		/// Move the expiry of a claim to `expires_at`, or make it permanent with `None`.
+		#[pallet::weight(T::WeightInfo::renew_claim())]
+		#[frame_support::transactional]
+		pub fn renew_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
+			let claim = Self::claim_id(algorithm, &digest)?;
+			let (owner, _, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			Self::unschedule_expiry(&claim);
+			if let Some(expires_at) = expires_at {
+				Self::schedule_expiry(&claim, expires_at)?;
+			}
+
+			Self::deposit_event(Event::ClaimRenewed(sender, claim, expires_at));
 
 			Ok(().into())
 		}
 
-		#[pallet::weight(0)]
-		pub fn revoke_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+		/// Hash a small `payload` on chain and claim it under the same key `create_claim` would
+		/// use for its digest.
+		#[pallet::weight(T::WeightInfo::create_claim_from_payload(payload.len() as u32))]
+		pub fn create_claim_from_payload(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			payload: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone())
-				.map_err(|_| Error::<T>::ClaimTooLong)?;
-			let (owner, _) = Proofs::<T>::get(&bounded_claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(payload.len() <= T::MaxClaimLength::get() as usize, Error::<T>::ClaimTooLong);
 
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			Self::do_create_claim(sender, ClaimId::from_payload(algorithm, &payload))?;
 
-			Proofs::<T>::remove(&bounded_claim);
+			Ok(().into())
+		}
 
-			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+		#[pallet::weight(T::WeightInfo::revoke_claim())]
+		pub fn revoke_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let claim = Self::claim_id(algorithm, &digest)?;
+			Self::do_revoke_claim(sender, claim)?;
 
 			Ok(().into())
 		}
 
-		#[pallet::weight(0)]
+		/// Hand a claim over to `dest`, recording the transfer and an optional `memo` in its
+		/// custody history.
+		#[pallet::weight(
+			T::WeightInfo::transfer_claim(memo.as_ref().map_or(0, |memo| memo.len() as u32))
+		)]
 		pub fn transfer_claim(
 			origin: OriginFor<T>,
 			dest: T::AccountId,
-			claim: Vec<u8>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			memo: Option<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let claim = Self::claim_id(algorithm, &digest)?;
+			let memo = memo
+				.map(|memo| BoundedVec::try_from(memo).map_err(|_| Error::<T>::MemoTooLong))
+				.transpose()?;
+			Self::do_transfer_claim(sender, dest, claim, memo)?;
+
+			Ok(().into())
+		}
+
+		/// Anchor the `root` of a Merkle tree over `leaf_count` document digests as one claim.
+		/// Any single document can then be proven with `verify_merkle_inclusion`.
+		#[pallet::weight(T::WeightInfo::create_merkle_claim())]
+		pub fn create_merkle_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			root: Vec<u8>,
+			leaf_count: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(leaf_count > 0, Error::<T>::InvalidLeafCount);
+			let claim = Self::claim_id(algorithm, &root)?;
+			Self::do_create_claim(sender, claim.clone())?;
+			MerkleRoots::<T>::insert(
+				&claim,
+				(leaf_count, frame_system::Pallet::<T>::block_number()),
+			);
+
+			Ok(().into())
+		}
+
+		/// Create one claim per `(algorithm, digest)` item.
+		#[pallet::weight(T::WeightInfo::create_claims(claims.len() as u32))]
+		#[frame_support::transactional]
+		pub fn create_claims(
+			origin: OriginFor<T>,
+			claims: Vec<(HashAlgorithm, Vec<u8>)>,
+			mode: BatchMode,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::run_batch(claims, mode, |(algorithm, digest)| {
+				let claim = Self::claim_id(algorithm, &digest)?;
+				Self::do_create_claim(sender.clone(), claim)
+			})
+		}
+
+		/// Revoke one claim per `(algorithm, digest)` item.
+		#[pallet::weight(T::WeightInfo::revoke_claims(claims.len() as u32))]
+		#[frame_support::transactional]
+		pub fn revoke_claims(
+			origin: OriginFor<T>,
+			claims: Vec<(HashAlgorithm, Vec<u8>)>,
+			mode: BatchMode,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::run_batch(claims, mode, |(algorithm, digest)| {
+				let claim = Self::claim_id(algorithm, &digest)?;
+				Self::do_revoke_claim(sender.clone(), claim)
+			})
+		}
+
+		/// Transfer one claim per `(dest, algorithm, digest)` item, without memos.
+		#[pallet::weight(T::WeightInfo::transfer_claims(claims.len() as u32))]
+		#[frame_support::transactional]
+		pub fn transfer_claims(
+			origin: OriginFor<T>,
+			claims: Vec<(T::AccountId, HashAlgorithm, Vec<u8>)>,
+			mode: BatchMode,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone())
-				.map_err(|_| Error::<T>::ClaimTooLong)?;
-			let (owner, _) = Proofs::<T>::get(&bounded_claim).ok_or(Error::<T>::ClaimNotExist)?;
+			Self::run_batch(claims, mode, |(dest, algorithm, digest)| {
+				let claim = Self::claim_id(algorithm, &digest)?;
+				Self::do_transfer_claim(sender.clone(), dest, claim, None)
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The deposit currently required to store `claim`.
+		pub fn deposit_for(claim: &ClaimId) -> BalanceOf<T> {
+			T::ClaimDeposit::get().saturating_mul((claim.encoded_size() as u32).into())
+		}
+
+		/// The owner of `claim` and the block it was created in. A claim transferred before its
+		/// custody history was kept reports the oldest block of that history instead.
+		pub fn claim(claim: &ClaimId) -> Option<(T::AccountId, T::BlockNumber)> {
+			let (owner, changed_at, _) = Proofs::<T>::get(claim)?;
+			// `record_custody` never prunes the first record, which is the creation of the claim.
+			let created_at =
+				ClaimHistory::<T>::get(claim).first().map_or(changed_at, |record| record.block);
+			Some((owner, created_at))
+		}
+
+		/// Up to `limit` claims of `owner` in storage order, starting after `start_after`. Pass
+		/// the last claim of a page to get the next one.
+		pub fn claims_of(
+			owner: &T::AccountId,
+			start_after: Option<ClaimId>,
+			limit: u32,
+		) -> Vec<ClaimId> {
+			let claims = match start_after {
+				Some(claim) => ClaimsByOwner::<T>::iter_key_prefix_from(
+					owner,
+					ClaimsByOwner::<T>::hashed_key_for(owner, claim),
+				),
+				None => ClaimsByOwner::<T>::iter_key_prefix(owner),
+			};
+			claims.take(limit as usize).collect()
+		}
+
+		/// The recorded chain of custody of `claim`, oldest first.
+		pub fn claim_history(claim: &ClaimId) -> Vec<CustodyRecordOf<T>> {
+			ClaimHistory::<T>::get(claim).into_inner()
+		}
+
+		/// Who held `claim` at block `at`, if the claim existed then and that part of its history
+		/// has not been pruned.
+		pub fn claim_holder_at(claim: &ClaimId, at: T::BlockNumber) -> Option<T::AccountId> {
+			let history = ClaimHistory::<T>::get(claim);
+			let index = history.iter().rposition(|record| record.block <= at)?;
+			if index == 0 && HistoryPrunedFrom::<T>::get(claim).map_or(false, |from| at >= from) {
+				return None
+			}
+			Some(history[index].holder.clone())
+		}
+
+		/// The block `root` was anchored in, if it is an anchored Merkle root and `proof` shows
+		/// that `leaf` is its leaf at `leaf_index`.
+		pub fn verify_merkle_inclusion(
+			root: &ClaimId,
+			leaf: &[u8],
+			leaf_index: u32,
+			proof: &[Vec<u8>],
+		) -> Option<T::BlockNumber> {
+			let (leaf_count, anchored_at) = MerkleRoots::<T>::get(root)?;
+			merkle::verify(root.algorithm, &root.digest, leaf, leaf_index, leaf_count, proof)
+				.then_some(anchored_at)
+		}
+
+		/// Append `record` to the history of `claim`, pruning the oldest transfer when full.
+		fn record_custody(claim: &ClaimId, record: CustodyRecordOf<T>) {
+			ClaimHistory::<T>::mutate(claim, |history| {
+				if history.len() as u32 >= T::MaxHistoryLength::get() && history.len() > 1 {
+					let pruned = history.remove(1);
+					HistoryPrunedFrom::<T>::mutate(claim, |from| {
+						from.get_or_insert(pruned.block);
+					});
+				}
+				let _ = history.try_push(record);
+			});
+		}
+
+		fn claim_id(algorithm: HashAlgorithm, digest: &[u8]) -> Result<ClaimId, Error<T>> {
+			ClaimId::new(algorithm, digest).ok_or(Error::<T>::InvalidDigestLength)
+		}
+
+		/// Apply `f` to every item. The caller must be transactional so that an
+		/// `AllOrNothing` failure reverts the items that already went through.
+		fn run_batch<I>(
+			items: Vec<I>,
+			mode: BatchMode,
+			f: impl Fn(I) -> DispatchResult,
+		) -> DispatchResultWithPostInfo {
+			ensure!(items.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+			for (index, item) in items.into_iter().enumerate() {
+				match mode {
+					BatchMode::AllOrNothing => f(item)?,
+					BatchMode::BestEffort =>
+						if let Err(error) = with_storage_layer(|| f(item)) {
+							Self::deposit_event(Event::BatchItemFailed(index as u32, error));
+						},
+				}
+			}
+
+			Ok(().into())
+		}
+
+		fn ensure_can_hold(owner: &T::AccountId) -> DispatchResult {
+			ensure!(
+				ClaimsByOwnerCount::<T>::get(owner) < T::MaxClaimsPerOwner::get(),
+				Error::<T>::TooManyClaims
+			);
+			Ok(())
+		}
+
+		fn add_owned(owner: &T::AccountId, claim: &ClaimId) {
+			ClaimsByOwner::<T>::insert(owner, claim, ());
+			ClaimsByOwnerCount::<T>::mutate(owner, |count| *count = count.saturating_add(1));
+		}
+
+		fn remove_owned(owner: &T::AccountId, claim: &ClaimId) {
+			if ClaimsByOwner::<T>::take(owner, claim).is_some() {
+				ClaimsByOwnerCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
+			}
+		}
+
+		fn do_create_claim(sender: T::AccountId, claim: ClaimId) -> DispatchResult {
+			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+			Self::ensure_can_hold(&sender)?;
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::NotEnoughBalance)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			Proofs::<T>::insert(&claim, (sender.clone(), now, deposit));
+			Self::add_owned(&sender, &claim);
+			Self::record_custody(
+				&claim,
+				CustodyRecord { holder: sender.clone(), block: now, memo: None },
+			);
+
+			Self::deposit_event(Event::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		fn do_revoke_claim(sender: T::AccountId, claim: ClaimId) -> DispatchResult {
+			let (owner, _, deposit) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
 
 			ensure!(owner == sender, Error::<T>::NotClaimOwner);
 
-			Proofs::<T>::insert(
-				&bounded_claim,
-				(dest.clone(), frame_system::Pallet::<T>::block_number()),
+			Self::remove_claim(&sender, &claim, deposit);
+
+			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+
+			Ok(())
+		}
+
+		/// Drop `claim` and everything stored alongside it, refunding `deposit` to `owner`.
+		fn remove_claim(owner: &T::AccountId, claim: &ClaimId, deposit: BalanceOf<T>) {
+			Self::unschedule_expiry(claim);
+			Proofs::<T>::remove(claim);
+			Self::remove_owned(owner, claim);
+			MerkleRoots::<T>::remove(claim);
+			ClaimHistory::<T>::remove(claim);
+			HistoryPrunedFrom::<T>::remove(claim);
+			T::Currency::unreserve(owner, deposit);
+		}
+
+		fn schedule_expiry(claim: &ClaimId, expires_at: T::BlockNumber) -> DispatchResult {
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
 			);
 
+			ExpiringClaims::<T>::try_mutate(expires_at, |expiring| {
+				expiring.try_push(claim.clone()).map_err(|_| Error::<T>::TooManyExpiring)
+			})?;
+			ClaimExpiry::<T>::insert(claim, expires_at);
+
+			Ok(())
+		}
+
+		fn unschedule_expiry(claim: &ClaimId) {
+			if let Some(expires_at) = ClaimExpiry::<T>::take(claim) {
+				ExpiringClaims::<T>::mutate_exists(expires_at, |maybe_expiring| {
+					if let Some(expiring) = maybe_expiring {
+						expiring.retain(|expiring_claim| expiring_claim != claim);
+						if expiring.is_empty() {
+							*maybe_expiring = None;
+						}
+					}
+				});
+			}
+		}
+
+		fn do_transfer_claim(
+			sender: T::AccountId,
+			dest: T::AccountId,
+			claim: ClaimId,
+			memo: Option<BoundedVec<u8, ConstU32<MAX_MEMO_LEN>>>,
+		) -> DispatchResult {
+			let (owner, _, deposit) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			if dest != sender {
+				Self::ensure_can_hold(&dest)?;
+			}
+
+			T::Currency::repatriate_reserved(&sender, &dest, deposit, BalanceStatus::Reserved)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			Proofs::<T>::insert(&claim, (dest.clone(), now, deposit));
+			Self::remove_owned(&sender, &claim);
+			Self::add_owned(&dest, &claim);
+			Self::record_custody(&claim, CustodyRecord { holder: dest.clone(), block: now, memo });
+
 			Self::deposit_event(Event::ClaimTransferred(sender, dest, claim));
-			Ok(().into())
+
+			Ok(())
 		}
 	}
 }
@@ -0,0 +1,107 @@
+//! Inclusion proofs against Merkle roots anchored with `create_merkle_claim`.
+//!
+//! Trees are built bottom-up from the leaf digests. Each leaf is hashed as `H(0x00 ++ leaf)` and
+//! each pair of nodes as `H(0x01 ++ left ++ right)` with the claim's algorithm, so a node can
+//! never pass for a leaf. An odd node at the end of a level is promoted to the next level
+//! unchanged. A proof lists, from the leaves up, the sibling at every level where the node has
+//! one; the leaf index and count tell where each sibling goes.
+
+use crate::claim::HashAlgorithm;
+use sp_std::vec::Vec;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn hash_leaf(algorithm: HashAlgorithm, leaf: &[u8]) -> Vec<u8> {
+	let mut data = Vec::with_capacity(1 + leaf.len());
+	data.push(LEAF_PREFIX);
+	data.extend_from_slice(leaf);
+	algorithm.hash(&data).into_inner()
+}
+
+fn hash_pair(algorithm: HashAlgorithm, left: &[u8], right: &[u8]) -> Vec<u8> {
+	let mut data = Vec::with_capacity(1 + left.len() + right.len());
+	data.push(NODE_PREFIX);
+	data.extend_from_slice(left);
+	data.extend_from_slice(right);
+	algorithm.hash(&data).into_inner()
+}
+
+fn leaf_level(algorithm: HashAlgorithm, leaves: &[Vec<u8>]) -> Vec<Vec<u8>> {
+	leaves.iter().map(|leaf| hash_leaf(algorithm, leaf)).collect()
+}
+
+fn next_level(algorithm: HashAlgorithm, level: &[Vec<u8>]) -> Vec<Vec<u8>> {
+	level
+		.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => hash_pair(algorithm, left, right),
+			_ => pair[0].clone(),
+		})
+		.collect()
+}
+
+/// The root of the tree over `leaves`, or `None` when there are no leaves.
+pub fn root(algorithm: HashAlgorithm, leaves: &[Vec<u8>]) -> Option<Vec<u8>> {
+	let mut level = leaf_level(algorithm, leaves);
+	while level.len() > 1 {
+		level = next_level(algorithm, &level);
+	}
+	level.pop()
+}
+
+/// The siblings proving the leaf at `leaf_index` of `leaves`, in the order `verify` expects.
+pub fn proof(algorithm: HashAlgorithm, leaves: &[Vec<u8>], leaf_index: u32) -> Vec<Vec<u8>> {
+	let mut proof = Vec::new();
+	let mut level = leaf_level(algorithm, leaves);
+	let mut index = leaf_index as usize;
+	while level.len() > 1 {
+		if let Some(sibling) = level.get(index ^ 1) {
+			proof.push(sibling.clone());
+		}
+		level = next_level(algorithm, &level);
+		index /= 2;
+	}
+	proof
+}
+
+/// Whether `leaf` is the leaf at `leaf_index` of a `leaf_count` leaf tree with the given `root`.
+/// The leaf and every sibling must be exactly one digest of `algorithm` long.
+pub fn verify(
+	algorithm: HashAlgorithm,
+	root: &[u8],
+	leaf: &[u8],
+	leaf_index: u32,
+	leaf_count: u32,
+	proof: &[Vec<u8>],
+) -> bool {
+	let digest_len = algorithm.digest_len();
+	if leaf_index >= leaf_count ||
+		leaf.len() != digest_len ||
+		proof.iter().any(|sibling| sibling.len() != digest_len)
+	{
+		return false
+	}
+
+	let mut node = hash_leaf(algorithm, leaf);
+	let mut index = leaf_index;
+	let mut width = leaf_count;
+	let mut siblings = proof.iter();
+	while width > 1 {
+		if index ^ 1 < width {
+			let sibling = match siblings.next() {
+				Some(sibling) => sibling,
+				None => return false,
+			};
+			node = if index % 2 == 0 {
+				hash_pair(algorithm, &node, sibling)
+			} else {
+				hash_pair(algorithm, sibling, &node)
+			};
+		}
+		index /= 2;
+		width = (width + 1) / 2;
+	}
+
+	siblings.next().is_none() && node == root
+}
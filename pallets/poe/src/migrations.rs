@@ -0,0 +1,120 @@
+//! Storage migrations for the PoE pallet.
+
+use crate::{claim::ClaimId, Config, HashAlgorithm, Pallet, Proofs};
+use frame_support::{
+	storage::{migration::storage_key_iter, unhashed},
+	traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+	Blake2_128Concat, BoundedVec,
+};
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+
+pub mod v1 {
+	use super::*;
+
+	/// Before v1 `Proofs` was keyed by the raw claim bytes.
+	type OldKey<T> = BoundedVec<u8, <T as Config>::MaxClaimLength>;
+	type OldValue<T> =
+		(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber);
+
+	/// Re-key every proof by the Blake2-256 digest of its old raw claim, the same key
+	/// `create_claim_from_payload` gives that content now.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		// Collect first: the new keys live under the same storage prefix as the old ones.
+		let old = storage_key_iter::<OldKey<T>, OldValue<T>, Blake2_128Concat>(
+			Pallet::<T>::name().as_bytes(),
+			b"Proofs",
+		)
+		.drain()
+		.collect::<Vec<_>>();
+
+		let count = old.len() as u64;
+		for (claim, value) in old {
+			// Written raw, as later versions change the value type of `Proofs`.
+			let key = Proofs::<T>::hashed_key_for(ClaimId::from_payload(
+				HashAlgorithm::Blake2_256,
+				&claim,
+			));
+			unhashed::put(&key, &value);
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(count + 1, 2 * count + 1)
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	type OldValue<T> =
+		(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber);
+
+	/// Record a zero deposit on every existing proof: nothing was reserved for them, so
+	/// revoking must not unreserve anything either.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated = 0u64;
+		Proofs::<T>::translate::<OldValue<T>, _>(|_, (owner, block)| {
+			translated += 1;
+			Some((owner, block, Zero::zero()))
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+pub mod v3 {
+	use super::*;
+	use crate::{ClaimHistory, CustodyRecord};
+
+	/// Seed the custody history of existing claims with their current holder. The stored block
+	/// is that of the last change, so earlier holders stay unknown.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		for (claim, (holder, block, _)) in Proofs::<T>::iter() {
+			count += 1;
+			ClaimHistory::<T>::mutate(&claim, |history| {
+				let _ = history.try_push(CustodyRecord { holder, block, memo: None });
+			});
+		}
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+}
+
+pub mod v4 {
+	use super::*;
+	use crate::{ClaimsByOwner, ClaimsByOwnerCount};
+
+	/// Build the `ClaimsByOwner` index from `Proofs`. Accounts already holding more than
+	/// `MaxClaimsPerOwner` claims keep them, but cannot gain new ones until they drop below it.
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 4 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		for (claim, (owner, _, _)) in Proofs::<T>::iter() {
+			count += 1;
+			ClaimsByOwner::<T>::insert(&owner, &claim, ());
+			ClaimsByOwnerCount::<T>::mutate(&owner, |held| *held = held.saturating_add(1));
+		}
+		StorageVersion::new(4).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(2 * count + 1, 2 * count + 1)
+	}
+}
@@ -1,7 +1,16 @@
 use super::*;
 use crate::{mock::*, Error};
 use frame_support::pallet_prelude::Get;
-use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+
+const BLAKE2: HashAlgorithm = HashAlgorithm::Blake2_256;
+
+/// Deposit for a 32 byte digest: the algorithm tag, the length prefix and the digest itself.
+const DEPOSIT: u64 = 34;
+
+fn claim_of(digest: &[u8]) -> ClaimId {
+	ClaimId::new(BLAKE2, digest).unwrap()
+}
 
 #[test]
 fn get_length() {
@@ -15,15 +24,25 @@ fn get_length() {
 fn create_claim_works() {
 	// create claim OK
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
-
-		let bounded_claim =
-			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
+		let digest = vec![1; 32];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None));
 
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
-			Some((1, frame_system::Pallet::<Test>::block_number()))
+			Proofs::<Test>::get(&claim_of(&digest)),
+			Some((1, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
+		);
+		assert_eq!(PoeModule::deposit_for(&claim_of(&digest)), DEPOSIT);
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+	})
+}
+
+#[test]
+fn create_claim_failed_when_not_enough_balance() {
+	// create claim Error NotEnoughBalance
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(4), BLAKE2, vec![1; 32], None),
+			Error::<Test>::NotEnoughBalance
 		);
 	})
 }
@@ -32,9 +51,9 @@ fn create_claim_works() {
 fn create_claim_failed_when_claim_too_long() {
 	// create claim Error ClaimTooLong
 	new_test_ext().execute_with(|| {
-		let claim = vec![0; 1000];
+		let payload = vec![0; 1000];
 		assert_noop!(
-			PoeModule::create_claim(Origin::signed(1), claim.clone()),
+			PoeModule::create_claim_from_payload(Origin::signed(1), BLAKE2, payload),
 			Error::<Test>::ClaimTooLong
 		);
 	})
@@ -44,11 +63,11 @@ fn create_claim_failed_when_claim_too_long() {
 fn create_claim_failed_when_claim_already_exist() {
 	// create claim Error ProofAlreadyExist
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
 
 		assert_noop!(
-			PoeModule::create_claim(Origin::signed(1), claim.clone()),
+			PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None),
 			Error::<Test>::ProofAlreadyExist
 		);
 	})
@@ -58,15 +77,14 @@ fn create_claim_failed_when_claim_already_exist() {
 fn revoke_claim_works() {
 	// revoke claim OK
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
-
-		let bounded_claim =
-			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
 
-		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), BLAKE2, digest.clone()));
 
-		assert_eq!(Proofs::<Test>::get(&bounded_claim), None);
+		assert_eq!(Proofs::<Test>::get(&claim_of(&digest)), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
 	})
 }
 
@@ -74,10 +92,10 @@ fn revoke_claim_works() {
 fn revoke_claim_failed_when_claim_not_exist() {
 	// revoke claim Error ClaimNotExist
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
+		let digest = vec![1; 32];
 
 		assert_noop!(
-			PoeModule::revoke_claim(Origin::signed(1), claim.clone()),
+			PoeModule::revoke_claim(Origin::signed(1), BLAKE2, digest.clone()),
 			Error::<Test>::ClaimNotExist
 		);
 	})
@@ -87,11 +105,11 @@ fn revoke_claim_failed_when_claim_not_exist() {
 fn revoke_claim_failed_when_not_claim_owner() {
 	// revoke claim Error NotClaimOwner
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
 
 		assert_noop!(
-			PoeModule::revoke_claim(Origin::signed(2), claim.clone()),
+			PoeModule::revoke_claim(Origin::signed(2), BLAKE2, digest.clone()),
 			Error::<Test>::NotClaimOwner
 		);
 	})
@@ -101,23 +119,26 @@ fn revoke_claim_failed_when_not_claim_owner() {
 fn transfer_claim_works() {
 	// transfer claim OK
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
-
-		let bounded_claim =
-			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
 
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
-			Some((1, frame_system::Pallet::<Test>::block_number()))
+			Proofs::<Test>::get(&claim_of(&digest)),
+			Some((1, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
 		);
 
-		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 2, claim.clone()));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 2, BLAKE2, digest.clone(), None));
 
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
-			Some((2, frame_system::Pallet::<Test>::block_number()))
+			Proofs::<Test>::get(&claim_of(&digest)),
+			Some((2, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
 		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000 - DEPOSIT);
+		assert_eq!(Balances::reserved_balance(2), DEPOSIT);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), BLAKE2, digest.clone()));
+		assert_eq!(Balances::free_balance(2), 1_000 + DEPOSIT);
 	})
 }
 
@@ -125,19 +146,16 @@ fn transfer_claim_works() {
 fn transfer_claim_failed_when_not_claim_owner() {
 	// transfer claim Error NotClaimOwner
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
-
-		let bounded_claim =
-			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
 
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
-			Some((1, frame_system::Pallet::<Test>::block_number()))
+			Proofs::<Test>::get(&claim_of(&digest)),
+			Some((1, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
 		);
 
 		assert_noop!(
-			PoeModule::transfer_claim(Origin::signed(2), 3, claim.clone()),
+			PoeModule::transfer_claim(Origin::signed(2), 3, BLAKE2, digest.clone(), None),
 			Error::<Test>::NotClaimOwner
 		);
 	})
@@ -147,22 +165,616 @@ fn transfer_claim_failed_when_not_claim_owner() {
 fn transfer_claim_failed_when_claim_not_exist() {
 	// transfer claim Error ClaimNotExist
 	new_test_ext().execute_with(|| {
-		let claim = vec![0, 1];
-		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim_of(&digest)),
+			Some((1, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
+		);
+
+		let digest2 = vec![2; 32];
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(2), 3, BLAKE2, digest2.clone(), None),
+			Error::<Test>::ClaimNotExist
+		);
+	})
+}
+
+#[test]
+fn create_claim_failed_when_digest_length_invalid() {
+	// create claim Error InvalidDigestLength
+	new_test_ext().execute_with(|| {
+		for algorithm in
+			[HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256]
+		{
+			assert_noop!(
+				PoeModule::create_claim(Origin::signed(1), algorithm, vec![0, 1], None),
+				Error::<Test>::InvalidDigestLength
+			);
+			assert_noop!(
+				PoeModule::create_claim(Origin::signed(1), algorithm, vec![0; 64], None),
+				Error::<Test>::InvalidDigestLength
+			);
+		}
+	})
+}
 
-		let bounded_claim =
-			BoundedVec::<u8, <Test as Config>::MaxClaimLength>::try_from(claim.clone()).unwrap();
+#[test]
+fn create_claim_from_payload_matches_digest() {
+	// both flows resolve to the same key
+	new_test_ext().execute_with(|| {
+		let payload = b"hello world".to_vec();
+		let digest = sp_io::hashing::sha2_256(&payload).to_vec();
+
+		assert_ok!(PoeModule::create_claim_from_payload(
+			Origin::signed(1),
+			HashAlgorithm::Sha2_256,
+			payload
+		));
 
+		assert_noop!(
+			PoeModule::create_claim(
+				Origin::signed(2),
+				HashAlgorithm::Sha2_256,
+				digest.clone(),
+				None
+			),
+			Error::<Test>::ProofAlreadyExist
+		);
 		assert_eq!(
-			Proofs::<Test>::get(&bounded_claim),
-			Some((1, frame_system::Pallet::<Test>::block_number()))
+			Proofs::<Test>::get(&ClaimId::new(HashAlgorithm::Sha2_256, &digest).unwrap()),
+			Some((1, frame_system::Pallet::<Test>::block_number(), DEPOSIT))
+		);
+	})
+}
+
+#[test]
+fn same_digest_with_other_algorithm_is_another_claim() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None));
+		assert_ok!(PoeModule::create_claim(
+			Origin::signed(2),
+			HashAlgorithm::Keccak256,
+			digest,
+			None
+		));
+	})
+}
+
+#[test]
+fn migrate_rehashes_raw_claims_without_deposit() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<PoeModule>();
+		let raw = vec![0u8, 1];
+		put_storage_value(
+			b"PoeModule",
+			b"Proofs",
+			&Blake2_128Concat::hash(&raw.encode()),
+			(1u64, 0u64),
 		);
 
-		let claim2 = vec![0, 2];
+		migrations::v1::migrate::<Test>();
+		assert_eq!(PoeModule::on_chain_storage_version(), 1);
+		migrations::v2::migrate::<Test>();
+		migrations::v3::migrate::<Test>();
+		migrations::v4::migrate::<Test>();
 
+		assert_eq!(Proofs::<Test>::get(&ClaimId::from_payload(BLAKE2, &raw)), Some((1, 0, 0)));
+		assert_eq!(Proofs::<Test>::iter().count(), 1);
+		assert_eq!(
+			PoeModule::claim_history(&ClaimId::from_payload(BLAKE2, &raw)),
+			vec![CustodyRecord { holder: 1, block: 0, memo: None }]
+		);
+		assert_eq!(ClaimsByOwnerCount::<Test>::get(1), 1);
+		assert_eq!(PoeModule::on_chain_storage_version(), 4);
+	})
+}
+
+#[test]
+fn transfer_claim_failed_when_memo_too_long() {
+	// transfer claim Error MemoTooLong
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let _ = PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None);
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), 2, BLAKE2, digest, Some(vec![0; 257])),
+			Error::<Test>::MemoTooLong
+		);
+	})
+}
+
+#[test]
+fn claim_history_records_custody() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let claim = claim_of(&digest);
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None));
+
+		System::set_block_number(5);
+		assert_ok!(PoeModule::transfer_claim(
+			Origin::signed(1),
+			2,
+			BLAKE2,
+			digest.clone(),
+			Some(b"sold".to_vec())
+		));
+
+		assert_eq!(
+			PoeModule::claim_history(&claim),
+			vec![
+				CustodyRecord { holder: 1, block: 1, memo: None },
+				CustodyRecord {
+					holder: 2,
+					block: 5,
+					memo: Some(b"sold".to_vec().try_into().unwrap())
+				},
+			]
+		);
+		assert_eq!(PoeModule::claim_holder_at(&claim, 0), None);
+		assert_eq!(PoeModule::claim_holder_at(&claim, 4), Some(1));
+		assert_eq!(PoeModule::claim_holder_at(&claim, 5), Some(2));
+		assert_eq!(PoeModule::claim_holder_at(&claim, 100), Some(2));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), BLAKE2, digest));
+		assert!(PoeModule::claim_history(&claim).is_empty());
+	})
+}
+
+#[test]
+fn claim_history_prunes_oldest_transfer() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let claim = claim_of(&digest);
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None));
+
+		for (block, (from, to)) in [(1, 2), (2, 3), (3, 1)].into_iter().enumerate() {
+			System::set_block_number(10 * (block as u64 + 1));
+			assert_ok!(PoeModule::transfer_claim(
+				Origin::signed(from),
+				to,
+				BLAKE2,
+				digest.clone(),
+				None
+			));
+		}
+
+		let history = PoeModule::claim_history(&claim);
+		assert_eq!(history.len(), 3);
+		assert_eq!((history[0].holder, history[0].block), (1, 1));
+		assert_eq!((history[1].holder, history[1].block), (3, 20));
+		assert_eq!((history[2].holder, history[2].block), (1, 30));
+
+		// The creator is kept, but who held the claim from block 10 to 20 is forgotten.
+		assert_eq!(PoeModule::claim_holder_at(&claim, 0), None);
+		assert_eq!(PoeModule::claim_holder_at(&claim, 5), Some(1));
+		assert_eq!(PoeModule::claim_holder_at(&claim, 15), None);
+		assert_eq!(PoeModule::claim_holder_at(&claim, 25), Some(3));
+	})
+}
+
+#[test]
+fn create_claims_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claims(
+			Origin::signed(1),
+			vec![(BLAKE2, vec![1; 32]), (BLAKE2, vec![2; 32])],
+			BatchMode::AllOrNothing
+		));
+		assert_eq!(Proofs::<Test>::iter().count(), 2);
+		assert_eq!(Balances::reserved_balance(1), 2 * DEPOSIT);
+
+		// The duplicate in the middle reverts the new claim before it.
 		assert_noop!(
-			PoeModule::transfer_claim(Origin::signed(2), 3, claim2.clone()),
+			PoeModule::create_claims(
+				Origin::signed(1),
+				vec![(BLAKE2, vec![3; 32]), (BLAKE2, vec![1; 32]), (BLAKE2, vec![4; 32])],
+				BatchMode::AllOrNothing
+			),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(Proofs::<Test>::get(&claim_of(&[3; 32])), None);
+	})
+}
+
+#[test]
+fn create_claims_best_effort_reports_failures() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![1; 32], None));
+
+		assert_ok!(PoeModule::create_claims(
+			Origin::signed(1),
+			vec![(BLAKE2, vec![3; 32]), (BLAKE2, vec![1; 32]), (BLAKE2, vec![0; 2])],
+			BatchMode::BestEffort
+		));
+
+		assert!(Proofs::<Test>::contains_key(&claim_of(&[3; 32])));
+		System::assert_has_event(mock::Event::PoeModule(crate::Event::BatchItemFailed(
+			1,
+			Error::<Test>::ProofAlreadyExist.into(),
+		)));
+		System::assert_last_event(mock::Event::PoeModule(crate::Event::BatchItemFailed(
+			2,
+			Error::<Test>::InvalidDigestLength.into(),
+		)));
+	})
+}
+
+#[test]
+fn batch_failed_when_too_large() {
+	new_test_ext().execute_with(|| {
+		let claims = (0..5).map(|i| (BLAKE2, vec![i; 32])).collect::<Vec<_>>();
+		assert_noop!(
+			PoeModule::create_claims(Origin::signed(1), claims, BatchMode::BestEffort),
+			Error::<Test>::BatchTooLarge
+		);
+	})
+}
+
+#[test]
+fn revoke_and_transfer_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claims = vec![(BLAKE2, vec![1; 32]), (BLAKE2, vec![2; 32])];
+		assert_ok!(PoeModule::create_claims(
+			Origin::signed(1),
+			claims.clone(),
+			BatchMode::AllOrNothing
+		));
+
+		assert_ok!(PoeModule::transfer_claims(
+			Origin::signed(1),
+			vec![(2, BLAKE2, vec![1; 32]), (3, BLAKE2, vec![2; 32])],
+			BatchMode::AllOrNothing
+		));
+		assert_eq!(Proofs::<Test>::get(&claim_of(&[1; 32])).unwrap().0, 2);
+		assert_eq!(Proofs::<Test>::get(&claim_of(&[2; 32])).unwrap().0, 3);
+
+		// Only the claim account 2 holds is revoked.
+		assert_ok!(PoeModule::revoke_claims(Origin::signed(2), claims, BatchMode::BestEffort));
+		assert_eq!(Proofs::<Test>::get(&claim_of(&[1; 32])), None);
+		assert!(Proofs::<Test>::contains_key(&claim_of(&[2; 32])));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		System::assert_last_event(mock::Event::PoeModule(crate::Event::BatchItemFailed(
+			1,
+			Error::<Test>::NotClaimOwner.into(),
+		)));
+	})
+}
+
+#[test]
+fn merkle_proofs_verify_for_every_leaf() {
+	for leaf_count in 1..=7u8 {
+		let leaves = (0..leaf_count).map(|i| vec![i; 32]).collect::<Vec<_>>();
+		let root = merkle::root(BLAKE2, &leaves).unwrap();
+
+		for (index, leaf) in leaves.iter().enumerate() {
+			let proof = merkle::proof(BLAKE2, &leaves, index as u32);
+			assert!(merkle::verify(BLAKE2, &root, leaf, index as u32, leaf_count as u32, &proof));
+
+			let other = (index as u32 + 1) % leaf_count as u32;
+			if other != index as u32 {
+				assert!(!merkle::verify(BLAKE2, &root, leaf, other, leaf_count as u32, &proof));
+			}
+		}
+	}
+}
+
+#[test]
+fn merkle_odd_node_is_promoted() {
+	let leaves = vec![vec![1; 32], vec![2; 32], vec![3; 32]];
+	let hash = |prefix: u8, nodes: &[&[u8]]| {
+		let mut data = vec![prefix];
+		nodes.iter().for_each(|node| data.extend_from_slice(node));
+		BLAKE2.hash(&data).into_inner()
+	};
+	let pair = hash(1, &[&hash(0, &[&leaves[0]]), &hash(0, &[&leaves[1]])]);
+	let root = hash(1, &[&pair, &hash(0, &[&leaves[2]])]);
+
+	assert_eq!(merkle::root(BLAKE2, &leaves), Some(root));
+	assert_eq!(merkle::proof(BLAKE2, &leaves, 2), vec![pair]);
+}
+
+#[test]
+fn merkle_forged_leaves_are_rejected() {
+	let leaves = (0..4u8).map(|i| vec![i; 32]).collect::<Vec<_>>();
+	let root = merkle::root(BLAKE2, &leaves).unwrap();
+	let proof = merkle::proof(BLAKE2, &leaves, 0);
+	assert!(merkle::verify(BLAKE2, &root, &leaves[0], 0, 4, &proof));
+
+	// Moving bytes of the first sibling into the leaf keeps their concatenation the same.
+	let mut forged_proof = proof.clone();
+	let split = forged_proof[0].split_off(8);
+	let forged_leaf = [leaves[0].clone(), forged_proof[0].clone()].concat();
+	forged_proof[0] = split;
+	assert!(!merkle::verify(BLAKE2, &root, &forged_leaf, 0, 4, &forged_proof));
+
+	// Two leaves cannot pass for one, with or without an empty sibling.
+	let pair = [leaves[0].clone(), leaves[1].clone()].concat();
+	assert!(!merkle::verify(BLAKE2, &root, &pair, 0, 4, &[vec![], proof[1].clone()]));
+
+	// Nor can an inner node pass for a leaf of a smaller tree.
+	let node = merkle::root(BLAKE2, &leaves[..2]).unwrap();
+	assert!(!merkle::verify(BLAKE2, &root, &node, 0, 2, &proof[1..]));
+}
+
+#[test]
+fn create_merkle_claim_works() {
+	new_test_ext().execute_with(|| {
+		let leaves = (0..5u8).map(|i| vec![i; 32]).collect::<Vec<_>>();
+		let root = merkle::root(BLAKE2, &leaves).unwrap();
+		let claim = claim_of(&root);
+
+		System::set_block_number(7);
+		assert_noop!(
+			PoeModule::create_merkle_claim(Origin::signed(1), BLAKE2, root.clone(), 0),
+			Error::<Test>::InvalidLeafCount
+		);
+		assert_ok!(PoeModule::create_merkle_claim(Origin::signed(1), BLAKE2, root.clone(), 5));
+		assert_eq!(MerkleRoots::<Test>::get(&claim), Some((5, 7)));
+
+		System::set_block_number(9);
+		let proof = merkle::proof(BLAKE2, &leaves, 3);
+		assert_eq!(PoeModule::verify_merkle_inclusion(&claim, &leaves[3], 3, &proof), Some(7));
+		assert_eq!(PoeModule::verify_merkle_inclusion(&claim, &[9; 32], 3, &proof), None);
+		assert_eq!(
+			PoeModule::verify_merkle_inclusion(&claim_of(&[9; 32]), &leaves[3], 3, &proof),
+			None
+		);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), BLAKE2, root));
+		assert_eq!(MerkleRoots::<Test>::get(&claim), None);
+		assert_eq!(PoeModule::verify_merkle_inclusion(&claim, &leaves[3], 3, &proof), None);
+	})
+}
+
+#[test]
+fn expired_claim_is_removed_on_initialize() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let claim = claim_of(&digest);
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), Some(5)));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 2, BLAKE2, digest, None));
+		assert_eq!(ClaimExpiry::<Test>::get(&claim), Some(5));
+		assert_eq!(ExpiringClaims::<Test>::get(5).into_inner(), vec![claim.clone()]);
+
+		System::set_block_number(5);
+		PoeModule::on_initialize(5);
+
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(ClaimExpiry::<Test>::get(&claim), None);
+		assert!(!ExpiringClaims::<Test>::contains_key(5));
+		assert!(PoeModule::claim_history(&claim).is_empty());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000 + DEPOSIT);
+		System::assert_last_event(mock::Event::PoeModule(crate::Event::ClaimExpired(2, claim)));
+	})
+}
+
+#[test]
+fn renew_claim_works() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let claim = claim_of(&digest);
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), Some(5)));
+		assert_ok!(PoeModule::renew_claim(Origin::signed(1), BLAKE2, digest.clone(), Some(8)));
+		System::assert_last_event(mock::Event::PoeModule(crate::Event::ClaimRenewed(
+			1,
+			claim.clone(),
+			Some(8),
+		)));
+
+		assert!(!ExpiringClaims::<Test>::contains_key(5));
+		PoeModule::on_initialize(5);
+		assert!(Proofs::<Test>::get(&claim).is_some());
+
+		assert_ok!(PoeModule::renew_claim(Origin::signed(1), BLAKE2, digest, None));
+		assert_eq!(ClaimExpiry::<Test>::get(&claim), None);
+		assert!(!ExpiringClaims::<Test>::contains_key(8));
+		PoeModule::on_initialize(8);
+		assert!(Proofs::<Test>::get(&claim).is_some());
+	})
+}
+
+#[test]
+fn revoke_claim_clears_expiry() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		let claim = claim_of(&digest);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), Some(5)));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), BLAKE2, digest));
+
+		assert_eq!(ClaimExpiry::<Test>::get(&claim), None);
+		assert!(!ExpiringClaims::<Test>::contains_key(5));
+	})
+}
+
+#[test]
+fn expiry_failed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![1; 32], Some(5)),
+			Error::<Test>::InvalidExpiry
+		);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![1; 32], Some(9)));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![2; 32], Some(9)));
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![3; 32], Some(9)),
+			Error::<Test>::TooManyExpiring
+		);
+
+		assert_noop!(
+			PoeModule::renew_claim(Origin::signed(2), BLAKE2, vec![1; 32], Some(10)),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::renew_claim(Origin::signed(1), BLAKE2, vec![3; 32], Some(10)),
 			Error::<Test>::ClaimNotExist
 		);
+		assert_noop!(
+			PoeModule::renew_claim(Origin::signed(1), BLAKE2, vec![1; 32], Some(4)),
+			Error::<Test>::InvalidExpiry
+		);
+	})
+}
+
+#[test]
+fn claim_returns_owner_and_creation_block() {
+	new_test_ext().execute_with(|| {
+		let digest = vec![1; 32];
+		assert_eq!(PoeModule::claim(&claim_of(&digest)), None);
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, digest.clone(), None));
+		assert_eq!(PoeModule::claim(&claim_of(&digest)), Some((1, 3)));
+
+		System::set_block_number(5);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 2, BLAKE2, digest.clone(), None));
+		assert_eq!(PoeModule::claim(&claim_of(&digest)), Some((2, 3)));
+
+		// Still the creation block once the history was pruned.
+		for (block, (from, to)) in (6..).zip([(2, 3), (3, 1), (1, 2)]) {
+			System::set_block_number(block);
+			assert_ok!(PoeModule::transfer_claim(
+				Origin::signed(from),
+				to,
+				BLAKE2,
+				digest.clone(),
+				None
+			));
+		}
+		assert!(HistoryPrunedFrom::<Test>::contains_key(claim_of(&digest)));
+		assert_eq!(PoeModule::claim(&claim_of(&digest)), Some((2, 3)));
+	})
+}
+
+#[test]
+fn claims_of_pages_through_owned_claims() {
+	new_test_ext().execute_with(|| {
+		for byte in 1..=5 {
+			assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![byte; 32], None));
+		}
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), BLAKE2, vec![9; 32], None));
+
+		let mut claims = Vec::new();
+		let mut start_after = None;
+		loop {
+			let page = PoeModule::claims_of(&1, start_after, 2);
+			if page.is_empty() {
+				break
+			}
+			assert!(page.len() <= 2);
+			start_after = page.last().cloned();
+			claims.extend(page);
+		}
+
+		claims.sort_by_key(|claim| claim.digest.to_vec());
+		assert_eq!(claims, (1..=5).map(|byte| claim_of(&[byte; 32])).collect::<Vec<_>>());
+		assert_eq!(PoeModule::claims_of(&2, None, 10), vec![claim_of(&[9; 32])]);
+	})
+}
+
+#[test]
+fn claims_by_owner_follows_create_transfer_revoke_and_expiry() {
+	new_test_ext().execute_with(|| {
+		let owned = |who: u64| {
+			let mut claims = ClaimsByOwner::<Test>::iter_key_prefix(who).collect::<Vec<_>>();
+			claims.sort_by_key(|claim| claim.digest.to_vec());
+			(claims, ClaimsByOwnerCount::<Test>::get(who))
+		};
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![1; 32], None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![2; 32], Some(5)));
+		assert_eq!(owned(1), (vec![claim_of(&[1; 32]), claim_of(&[2; 32])], 2));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 2, BLAKE2, vec![2; 32], None));
+		assert_eq!(owned(1), (vec![claim_of(&[1; 32])], 1));
+		assert_eq!(owned(2), (vec![claim_of(&[2; 32])], 1));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 1, BLAKE2, vec![1; 32], None));
+		assert_eq!(owned(1), (vec![claim_of(&[1; 32])], 1));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), BLAKE2, vec![1; 32]));
+		assert_eq!(owned(1), (vec![], 0));
+
+		System::set_block_number(5);
+		PoeModule::on_initialize(5);
+		assert_eq!(owned(2), (vec![], 0));
+	})
+}
+
+#[test]
+fn claims_per_owner_are_limited() {
+	new_test_ext().execute_with(|| {
+		let max = <<Test as Config>::MaxClaimsPerOwner as Get<u32>>::get() as u8;
+		for byte in 1..=max {
+			assert_ok!(PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![byte; 32], None));
+		}
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), BLAKE2, vec![0; 32], None),
+			Error::<Test>::TooManyClaims
+		);
+		assert_noop!(
+			PoeModule::create_claim_from_payload(Origin::signed(1), BLAKE2, vec![0]),
+			Error::<Test>::TooManyClaims
+		);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), BLAKE2, vec![0; 32], None));
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(2), 1, BLAKE2, vec![0; 32], None),
+			Error::<Test>::TooManyClaims
+		);
+		// Moving a claim to its own owner does not need room.
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), 1, BLAKE2, vec![1; 32], None));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), BLAKE2, vec![1; 32]));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), 1, BLAKE2, vec![0; 32], None));
+		assert_eq!(ClaimsByOwnerCount::<Test>::get(1), max as u32);
+	})
+}
+
+#[test]
+fn migrate_builds_claims_by_owner() {
+	use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<PoeModule>();
+		Proofs::<Test>::insert(claim_of(&[1; 32]), (1, 0, 0));
+		Proofs::<Test>::insert(claim_of(&[2; 32]), (1, 0, 0));
+		Proofs::<Test>::insert(claim_of(&[3; 32]), (2, 0, 0));
+
+		migrations::v4::migrate::<Test>();
+
+		assert_eq!(ClaimsByOwnerCount::<Test>::get(1), 2);
+		assert_eq!(ClaimsByOwnerCount::<Test>::get(2), 1);
+		assert!(ClaimsByOwner::<Test>::contains_key(1, claim_of(&[1; 32])));
+		assert!(ClaimsByOwner::<Test>::contains_key(1, claim_of(&[2; 32])));
+		assert_eq!(PoeModule::claims_of(&2, None, 10), vec![claim_of(&[3; 32])]);
+		assert_eq!(PoeModule::on_chain_storage_version(), 4);
+
+		// Running it again must not count the claims twice.
+		migrations::v4::migrate::<Test>();
+		assert_eq!(ClaimsByOwnerCount::<Test>::get(1), 2);
 	})
 }
@@ -0,0 +1,304 @@
+//! Weights for pallet_poe
+//!
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS. The storage comments and database
+//! read/write counts follow what each call touches; the base and per-item times are rough
+//! guesses. Regenerate this file with `scripts/benchmark_weights.sh` before relying on it for
+//! fees.
+
+// Command to regenerate:
+// ./target/release/node-template
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_poe
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --template=.maintain/frame-weight-template.hbs
+// --output=pallets/poe/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim() -> Weight;
+	fn create_claim_from_payload(l: u32, ) -> Weight;
+	fn renew_claim() -> Weight;
+	fn revoke_claim() -> Weight;
+	fn transfer_claim(m: u32, ) -> Weight;
+	fn create_merkle_claim() -> Weight;
+	fn create_claims(n: u32, ) -> Weight;
+	fn revoke_claims(n: u32, ) -> Weight;
+	fn transfer_claims(n: u32, ) -> Weight;
+	fn on_initialize(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_poe using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claim() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claim_from_payload(l: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:0)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:2 w:2)
+	fn renew_claim() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn revoke_claim() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:2 w:2)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:2)
+	fn transfer_claim(m: u32, ) -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_merkle_claim() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claims(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn revoke_claims(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:2 w:2)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:2)
+	fn transfer_claims(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((35_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((32_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claim() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claim_from_payload(l: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:0)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:2 w:2)
+	fn renew_claim() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn revoke_claim() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:2 w:2)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:2)
+	fn transfer_claim(m: u32, ) -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_merkle_claim() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:0 w:1)
+	fn create_claims(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((25_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn revoke_claims(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PoeModule ClaimHistory (r:1 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:2 w:2)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:2)
+	fn transfer_claims(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((35_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PoeModule ExpiringClaims (r:1 w:1)
+	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule ClaimExpiry (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PoeModule MerkleRoots (r:0 w:1)
+	// Storage: PoeModule ClaimHistory (r:0 w:1)
+	// Storage: PoeModule HistoryPrunedFrom (r:0 w:1)
+	// Storage: PoeModule ClaimsByOwner (r:1 w:1)
+	// Storage: PoeModule ClaimsByOwnerCount (r:1 w:1)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((32_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -103,10 +103,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 106,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 4,
 	state_version: 1,
 };
 
@@ -145,6 +145,7 @@ parameter_types! {
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 	pub const SS58Prefix: u8 = 42;
 	pub const KittyReserve: u64 =100;
+	pub const KittyMutationChance: Permill = Permill::from_percent(2);
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -328,12 +329,25 @@ Call: From<C>,
 impl pallet_ocw::Config for Runtime {
 	type Event = Event;
 	type AuthorityId = pallet_ocw::crypto::OcwAuthId;
+	type MaxDataSources = ConstU32<8>;
+	type MaxPricesPerAccount = ConstU32<16>;
+	type MaxPriceAge = ConstU32<100>;
+	type MaxAuthorities = ConstU32<32>;
+	type UnsignedPriority = ConstU64<10_000>;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 /// Configure the PoE pallet
 impl pallet_poe::Config for Runtime {
 	type MaxClaimLength = ConstU32<512>;
 	type Event = Event;
+	type Currency = Balances;
+	type ClaimDeposit = ConstU128<10>;
+	type MaxHistoryLength = ConstU32<32>;
+	type MaxBatchSize = ConstU32<256>;
+	type MaxExpiringPerBlock = ConstU32<64>;
+	type MaxClaimsPerOwner = ConstU32<1024>;
+	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the Kitties pallet
@@ -351,8 +365,12 @@ impl pallet_kitties::Config for Runtime {
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type KittyIndex = u32;
-	type MaxKittyIndex = ConstU32<512>;
 	type KittyPrice = ConstU128<512>;
+	type MaxAuctionDuration = ConstU32<{ 7 * DAYS }>;
+	type MaxAuctionsPerBlock = ConstU32<16>;
+	type BreedCooldown = ConstU32<{ 10 * MINUTES }>;
+	type MutationChance = KittyMutationChance;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -420,6 +438,8 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_poe, PoeModule]
+		[pallet_kitties, KittiesModule]
 	);
 }
 
@@ -554,6 +574,63 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, u32, BlockNumber> for Runtime {
+		fn kitty(kitty_id: u32) -> Option<pallet_kitties::Kitty<u32, BlockNumber>> {
+			KittiesModule::kitties(kitty_id)
+		}
+
+		fn genome(kitty_id: u32) -> Option<pallet_kitties::Genome> {
+			KittiesModule::genome(kitty_id)
+		}
+
+		fn kitties_of(owner: AccountId, start_after: Option<u32>, limit: u32) -> Vec<u32> {
+			KittiesModule::kitties_of_page(&owner, start_after, limit)
+		}
+
+		fn kitty_details(
+			kitty_id: u32,
+		) -> Option<pallet_kitties::KittyDetails<AccountId, u32, BlockNumber>> {
+			KittiesModule::kitty_details(kitty_id)
+		}
+
+		fn kitty_count() -> u32 {
+			KittiesModule::kitty_count()
+		}
+	}
+
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn claim(claim: pallet_poe::ClaimId) -> Option<(AccountId, BlockNumber)> {
+			PoeModule::claim(&claim)
+		}
+
+		fn claims_of(
+			owner: AccountId,
+			start_after: Option<pallet_poe::ClaimId>,
+			limit: u32,
+		) -> Vec<pallet_poe::ClaimId> {
+			PoeModule::claims_of(&owner, start_after, limit)
+		}
+
+		fn claim_history(
+			claim: pallet_poe::ClaimId,
+		) -> Vec<pallet_poe::CustodyRecord<AccountId, BlockNumber>> {
+			PoeModule::claim_history(&claim)
+		}
+
+		fn claim_holder_at(claim: pallet_poe::ClaimId, at: BlockNumber) -> Option<AccountId> {
+			PoeModule::claim_holder_at(&claim, at)
+		}
+
+		fn verify_merkle_inclusion(
+			root: pallet_poe::ClaimId,
+			leaf: Vec<u8>,
+			leaf_index: u32,
+			proof: Vec<Vec<u8>>,
+		) -> Option<BlockNumber> {
+			PoeModule::verify_merkle_inclusion(&root, &leaf, leaf_index, &proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
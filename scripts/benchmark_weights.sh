@@ -0,0 +1,31 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+# Regenerate the `weights.rs` of the custom pallets from their benchmarks.
+set -e
+
+cd "$(dirname "$0")/.."
+
+PALLETS=(
+	"pallet_poe:pallets/poe/src/weights.rs"
+	"pallet_kitties:pallets/kitties/src/weights.rs"
+)
+
+echo "*** Building the node with runtime benchmarks ***"
+cargo build --release --features runtime-benchmarks
+
+for entry in "${PALLETS[@]}"; do
+	pallet="${entry%%:*}"
+	output="${entry#*:}"
+
+	echo "*** Benchmarking $pallet into $output ***"
+	./target/release/node-template benchmark pallet \
+		--chain=dev \
+		--steps=50 \
+		--repeat=20 \
+		--pallet="$pallet" \
+		--extrinsic='*' \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--template=.maintain/frame-weight-template.hbs \
+		--output="$output"
+done